@@ -40,11 +40,22 @@ const tydesc_field_align: int = 2;
 const tydesc_field_take_glue: int = 3;
 const tydesc_field_drop_glue: int = 4;
 const tydesc_field_free_glue: int = 5;
-const tydesc_field_unused: int = 6;
+// Non-null iff ty::type_needs_drop was true for this type (see
+// declare_tydesc/emit_tydescs in trans/base.rs) -- lets generic code
+// (libcore::sys::needs_drop) query at runtime whether a type is trivially
+// droppable without a real glue call. Repurposes what was a plain
+// unused/reserved slot, the same way tydesc_field_pref_align below
+// repurposes the other one.
+const tydesc_field_needs_drop: int = 6;
 const tydesc_field_sever_glue: int = 7;
 const tydesc_field_mark_glue: int = 8;
-const tydesc_field_unused2: int = 9;
-const tydesc_field_unused_2: int = 10;
+// Preferred (as opposed to ABI-mandated; see llalign_of/llalign_of_pref in
+// trans/shape.rs) alignment of the type, e.g. 8 rather than 4 for f64 on
+// x86. Repurposes what was a second unused/reserved slot -- the runtime
+// never read or wrote it (rust_internal.h's type_desc now names it
+// pref_align), so no other field's index needed to move.
+const tydesc_field_pref_align: int = 9;
+const tydesc_field_cmp_glue: int = 10;
 const tydesc_field_shape: int = 11;
 const tydesc_field_shape_tables: int = 12;
 const tydesc_field_n_params: int = 13;
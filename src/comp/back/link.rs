@@ -210,6 +210,18 @@ mod write {
               3u { CodeGenOptLevel = LLVMOptAggressive; }
             }
 
+            let LLVMCodeModelSmall  = 2 as c_int;
+            let LLVMCodeModelKernel = 3 as c_int;
+            let LLVMCodeModelMedium = 4 as c_int;
+            let LLVMCodeModelLarge  = 5 as c_int;
+            let CodeModel = alt opts.code_model {
+              "small" { LLVMCodeModelSmall }
+              "medium" { LLVMCodeModelMedium }
+              "large" { LLVMCodeModelLarge }
+              "kernel" { LLVMCodeModelKernel }
+              _ { sess.bug("unknown code model: " + opts.code_model); }
+            };
+
             let FileType;
             if opts.output_type == output_type_object ||
                    opts.output_type == output_type_exe {
@@ -241,6 +253,7 @@ mod write {
                                     buf_o,
                                     LLVMAssemblyFile,
                                     CodeGenOptLevel,
+                                    CodeModel,
                                     true)})});
                 }
 
@@ -261,6 +274,7 @@ mod write {
                                         buf_o,
                                         LLVMObjectFile,
                                         CodeGenOptLevel,
+                                        CodeModel,
                                         true)})});
                 }
             } else {
@@ -279,6 +293,7 @@ mod write {
                                     buf_o,
                                     FileType,
                                     CodeGenOptLevel,
+                                    CodeModel,
                                     true)})});
             }
             // Clean up and return
@@ -1,6 +1,7 @@
 type t = {
     module_asm: str,
     meta_sect_name: str,
+    shape_sect_name: str,
     data_layout: str,
     target_triple: str,
     cc_args: [str]
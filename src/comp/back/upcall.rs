@@ -31,12 +31,14 @@ type upcalls =
      call_shim_on_c_stack: ValueRef,
      call_shim_on_rust_stack: ValueRef,
      rust_personality: ValueRef,
-     reset_stack_limit: ValueRef};
+     reset_stack_limit: ValueRef,
+     heap_profile: ValueRef};
 
 fn declare_upcalls(targ_cfg: @session::config,
                    _tn: type_names,
                    tydesc_type: TypeRef,
-                   llmod: ModuleRef) -> @upcalls {
+                   llmod: ModuleRef,
+                   personality: option<str>) -> @upcalls {
     fn decl(llmod: ModuleRef, prefix: str, name: str,
             tys: [TypeRef], rv: TypeRef) ->
        ValueRef {
@@ -55,7 +57,9 @@ fn declare_upcalls(targ_cfg: @session::config,
 
     ret @{_fail: dv("fail", [T_ptr(T_i8()),
                              T_ptr(T_i8()),
-                             size_t]),
+                             size_t,
+                             size_t,
+                             T_ptr(T_i8())]),
           malloc:
               d("malloc", [T_ptr(tydesc_type)], T_ptr(T_i8())),
           free:
@@ -114,9 +118,19 @@ fn declare_upcalls(targ_cfg: @session::config,
               d("call_shim_on_rust_stack",
                 [T_ptr(T_i8()), T_ptr(T_i8())], int_t),
           rust_personality:
-              d("rust_personality", [], T_i32()),
+              alt personality {
+                // An overridden personality is an arbitrary external
+                // symbol (e.g. __gxx_personality_v0 or a SEH handler),
+                // not one of our upcall_-prefixed runtime entry points.
+                some(name) {
+                    base::decl_cdecl_fn(llmod, name, T_fn([], T_i32()))
+                }
+                none { d("rust_personality", [], T_i32()) }
+              },
           reset_stack_limit:
-              dv("reset_stack_limit", [])
+              dv("reset_stack_limit", []),
+          heap_profile:
+              dv("heap_profile", [T_ptr(T_i8()), int_t, size_t])
          };
 }
 //
@@ -11,6 +11,13 @@ fn get_target_strs(target_os: session::os) -> target_strs::t {
           session::os_freebsd { ".note.rustc" }
         },
 
+        shape_sect_name: alt target_os {
+          session::os_macos { "__DATA,__rust_shape" }
+          session::os_win32 { ".rust_shape" }
+          session::os_linux { ".rust_shape" }
+          session::os_freebsd { ".rust_shape" }
+        },
+
         data_layout: alt target_os {
           session::os_macos {
             "e-p:32:32:32-i1:8:8-i8:8:8-i16:16:16" + "-i32:32:32-i64:32:64" +
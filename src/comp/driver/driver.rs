@@ -356,6 +356,7 @@ fn build_session_options(match: getopts::match,
         lint_opts += [(lint::ctypes, false)];
     }
     let monomorphize = opt_present(match, "monomorphize");
+    let shared_glue = opt_present(match, "shared-glue");
 
     let output_type =
         if parse_only || no_trans {
@@ -371,8 +372,13 @@ fn build_session_options(match: getopts::match,
         } else { link::output_type_exe };
     let verify = !opt_present(match, "no-verify");
     let save_temps = opt_present(match, "save-temps");
-    let extra_debuginfo = opt_present(match, "xg");
-    let debuginfo = opt_present(match, "g") || extra_debuginfo;
+    // -g: line tables only (level 1). -xg: full variable/type metadata
+    // on top of that (level 2). See the doc comment on
+    // session::options's debuginfo field.
+    let debuginfo: uint =
+        if opt_present(match, "xg") { 2u }
+        else if opt_present(match, "g") { 1u }
+        else { 0u };
     let stats = opt_present(match, "stats");
     let time_passes = opt_present(match, "time-passes");
     let time_llvm_passes = opt_present(match, "time-llvm-passes");
@@ -412,12 +418,29 @@ fn build_session_options(match: getopts::match,
     let cfg = parse_cfgspecs(getopts::opt_strs(match, "cfg"));
     let test = opt_present(match, "test");
     let warn_unused_imports = opt_present(match, "warn-unused-imports");
+    let personality = getopts::opt_maybe_str(match, "personality");
+    let heap_profile = opt_present(match, "heap-profile");
+    let structured_cfg = opt_present(match, "structured-cfg");
+    let relative_vtables = opt_present(match, "relative-vtables");
+    let instrument_functions = opt_present(match, "instrument-functions");
+    let target_data_layout = getopts::opt_maybe_str(match, "data-layout");
+    let code_model =
+        alt getopts::opt_maybe_str(match, "code-model") {
+          none { "small" }
+          some("small") { "small" }
+          some("medium") { "medium" }
+          some("large") { "large" }
+          some("kernel") { "kernel" }
+          some(m) {
+            early_error(demitter, "code model needs to be one of " +
+                        "small, medium, large, or kernel, found: " + m)
+          }
+        };
     let sopts: @session::options =
         @{crate_type: crate_type,
           static: static,
           optimize: opt_level,
           debuginfo: debuginfo,
-          extra_debuginfo: extra_debuginfo,
           verify: verify,
           lint_opts: lint_opts,
           save_temps: save_temps,
@@ -434,7 +457,15 @@ fn build_session_options(match: getopts::match,
           no_trans: no_trans,
           no_asm_comments: no_asm_comments,
           monomorphize: monomorphize,
-          warn_unused_imports: warn_unused_imports};
+          shared_glue: shared_glue,
+          warn_unused_imports: warn_unused_imports,
+          personality: personality,
+          heap_profile: heap_profile,
+          structured_cfg: structured_cfg,
+          relative_vtables: relative_vtables,
+          code_model: code_model,
+          instrument_functions: instrument_functions,
+          target_data_layout: target_data_layout};
     ret sopts;
 }
 
@@ -504,10 +535,18 @@ fn opts() -> [getopts::opt] {
          optflag("no-verify"),
          optflag("no-lint-ctypes"),
          optflag("monomorphize"),
+         optflag("shared-glue"),
          optmulti("cfg"), optflag("test"),
          optflag("lib"), optflag("bin"), optflag("static"), optflag("gc"),
          optflag("no-asm-comments"),
-         optflag("warn-unused-imports")];
+         optflag("warn-unused-imports"),
+         optopt("personality"),
+         optflag("heap-profile"),
+         optflag("structured-cfg"),
+         optflag("relative-vtables"),
+         optflag("instrument-functions"),
+         optopt("data-layout"),
+         optopt("code-model")];
 }
 
 type output_filenames = @{out_filename: str, obj_filename:str};
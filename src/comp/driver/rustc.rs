@@ -27,8 +27,13 @@ Options:
     -c                 Compile and assemble, but do not link
     --cfg <cfgspec>    Configure the compilation environment
     --emit-llvm        Produce an LLVM bitcode file
-    -g                 Produce debug info
+    -g                 Produce debug info (line tables only; enough for
+                       accurate backtraces)
+    --xg               Produce full debug info, including variable and
+                       type metadata
     --gc               Garbage collect shared data (experimental/temporary)
+    --heap-profile     Report the file, line, and size of every heap
+                       allocation to an external profiling hook
     -h --help          Display this message
     -L <path>          Add a directory to the library search path
     --lib              Compile a library crate
@@ -43,16 +48,26 @@ Options:
     --opt-level <lvl>  Optimize with possible levels 0-3
     --out-dir <dir>    Write output to compiler-chosen filename in <dir>
     --parse-only       Parse only; do not compile, assemble, or link
+    --personality <fn> Reference <fn> as the EH personality routine
+                       instead of rust_personality (e.g. for a C++ or
+                       SEH runtime)
     --pretty [type]    Pretty-print the input instead of compiling;
                        valid types are: normal (un-annotated source), 
                        expanded (crates expanded), typed (crates expanded,
                        with type annotations), or identified (fully
                        parenthesized, AST nodes and blocks with IDs)
+    --relative-vtables Emit impl vtables as tables of 32-bit offsets from
+                       the vtable instead of absolute function pointers,
+                       shrinking vtables and relocation count in PIC
+                       binaries
     -S                 Compile only; do not assemble or link
     --save-temps       Write intermediate files (.bc, .opt.bc, .o)
                        in addition to normal output
     --static           Use or produce static libraries or binaries
     --stats            Print compilation statistics
+    --structured-cfg   Reject break/continue nested inside an if/loop
+                       (constrained-codegen mode for a future relooper-
+                       free wasm lowering; not a wasm backend)
     --sysroot <path>   Override the system root
     --test             Build a test harness
     --target <triple>  Target cpu-manufacturer-kernel[-os] to compile for
@@ -28,8 +28,12 @@ type options =
     {crate_type: crate_type,
      static: bool,
      optimize: uint,
-     debuginfo: bool,
-     extra_debuginfo: bool,
+     // 0: no debug info. 1: line tables only (enough for accurate
+     // backtraces, via update_source_pos), no per-local/per-arg variable
+     // or type metadata. 2: full debug info, adding create_local_var/
+     // create_arg/create_function's variable and type metadata on top of
+     // the line tables.
+     debuginfo: uint,
      verify: bool,
      lint_opts: [(lint::option, bool)],
      save_temps: bool,
@@ -46,7 +50,54 @@ type options =
      no_trans: bool,
      no_asm_comments: bool,
      monomorphize: bool,
-     warn_unused_imports: bool};
+     shared_glue: bool,
+     warn_unused_imports: bool,
+     personality: option<str>,
+     heap_profile: bool,
+     // Rejects a break/continue that isn't directly in its enclosing
+     // loop's own body scope, since such a break/continue crosses scope
+     // boundaries in a way that isn't properly nested (see the doc
+     // comment on trans_break_cont in trans/base.rs). This is a
+     // constrained-codegen mode, not a real wasm backend: it exists so
+     // code destined for a future structured-control-flow (relooper-free)
+     // wasm lowering can be checked ahead of time, not so this tree can
+     // emit wasm today.
+     structured_cfg: bool,
+     // Emits impl vtables (see trans_vtable in trans/impl.rs) as arrays
+     // of 32-bit offsets from the vtable to each method, rather than
+     // absolute function pointers -- method dispatch (trans_vtable_callee)
+     // adds the offset back to the vtable's own address to recover a
+     // callable pointer. This halves vtable size on 64-bit targets and,
+     // since offsets between two symbols in the same module need no
+     // relocation while absolute pointers to code do, cuts relocation
+     // count in PIC binaries.
+     relative_vtables: bool,
+     // No relocation_model option alongside this one yet: doing PIC builds
+     // properly would also need a Reloc parameter threaded through
+     // LLVMRustWriteOutputFile (see the CodeModel handling next to its call
+     // sites in back::link) and, on the trans side, changing how
+     // get_extern_const's callers address externals when the GOT is
+     // required. code_model is as far as that per-target codegen surface
+     // reaches today.
+     code_model: str,
+     // Has trans_closure/finish_fn emit calls to the gcc-compatible
+     // __cyg_profile_func_enter/__cyg_profile_func_exit hooks (declared via
+     // get_extern_fn the same way the __cxa_begin_catch/__cxa_end_catch
+     // externs are) around every function body, so external sampling-free
+     // profilers can trace calls without recompiling with a particular
+     // profiler's own instrumentation. Off by default: it adds two extern
+     // calls to every function, which no ordinary build wants.
+     instrument_functions: bool,
+     // Overrides target_strs::t's data_layout for this compilation, for
+     // target sub-variants (x32, hard/soft-float ARM, ...) sharing a
+     // target_triple/arch/os but not a layout. Threaded into both
+     // LLVMSetDataLayout and lib::llvm::mk_target_data (trans::base::
+     // trans_crate) in place of targ_cfg.target_strs.data_layout, so they
+     // never disagree. Not syntax-checked: LLVMCreateTargetData has no
+     // fallible-parse entry point in the C API this tree binds, so a
+     // malformed string here surfaces however LLVM itself reacts to it,
+     // same as target_strs::t's own hand-written per-target defaults.
+     target_data_layout: option<str>};
 
 type crate_metadata = {name: str, data: [u8]};
 
@@ -29,6 +29,27 @@ export mk_list_item;
 export mk_word_item;
 export mk_attr;
 export native_abi;
+export inline_attr;
+export il_none;
+export il_hint;
+export il_always;
+export il_never;
+export find_inline_attr;
+export instruction_set;
+export is_none;
+export is_arm;
+export is_thumb;
+export find_instruction_set_attr;
+export repr_attr;
+export repr_none;
+export repr_c;
+export repr_int;
+export find_repr_attr;
+export fn_call_conv;
+export fcc_rust;
+export fcc_fastcall;
+export fcc_stdcall;
+export find_fn_call_conv;
 
 // From a list of crate attributes get only the meta_items that impact crate
 // linkage
@@ -224,6 +245,95 @@ fn native_abi(attrs: [ast::attribute]) -> either::t<str, ast::native_abi> {
     };
 }
 
+enum inline_attr { il_none, il_hint, il_always, il_never, }
+
+// Determine what, if any, inlining is requested for a function via its
+// attributes. Last attribute wins, so that a re-exported/derived item can
+// override an inherited one; a bare `#[inline]` is a hint, while
+// `#[inline(always)]`/`#[inline(never)]` are directives.
+fn find_inline_attr(attrs: [ast::attribute]) -> inline_attr {
+    let attr = il_none;
+    for a: ast::attribute in find_attrs_by_name(attrs, "inline") {
+        attr = alt attr_meta(a).node {
+          ast::meta_word(_) { il_hint }
+          ast::meta_list(_, items) if items.len() > 0u {
+            alt get_meta_item_name(items[0]) {
+              "always" { il_always }
+              "never" { il_never }
+              _ { attr }
+            }
+          }
+          _ { attr }
+        };
+    }
+    ret attr;
+}
+
+enum instruction_set { is_none, is_arm, is_thumb, }
+
+// Determine the `#[instruction_set(arm)]`/`#[instruction_set(thumb)]`
+// override, if any, requested for a function. Like find_inline_attr, the
+// last attribute wins.
+fn find_instruction_set_attr(attrs: [ast::attribute]) -> instruction_set {
+    let iset = is_none;
+    for a: ast::attribute in find_attrs_by_name(attrs, "instruction_set") {
+        iset = alt attr_meta(a).node {
+          ast::meta_list(_, items) if items.len() > 0u {
+            alt get_meta_item_name(items[0]) {
+              "arm" { is_arm }
+              "thumb" { is_thumb }
+              _ { iset }
+            }
+          }
+          _ { iset }
+        };
+    }
+    ret iset;
+}
+
+enum repr_attr { repr_none, repr_c, repr_int(ast::uint_ty), }
+
+// Determine the `#[repr(...)]` requested for a type, if any. Only
+// recognizes `C` and the fixed-width unsigned ints; anything else is
+// ignored rather than rejected here (leaving repr_none if no earlier repr
+// attribute was recognized), since parsing the attribute doesn't commit
+// trans to acting on it. The last recognized attribute wins.
+fn find_repr_attr(attrs: [ast::attribute]) -> repr_attr {
+    let repr = repr_none;
+    for a: ast::attribute in find_attrs_by_name(attrs, "repr") {
+        repr = alt attr_meta(a).node {
+          ast::meta_list(_, items) if items.len() > 0u {
+            alt get_meta_item_name(items[0]) {
+              "C" { repr_c }
+              "u8" { repr_int(ast::ty_u8) }
+              "u16" { repr_int(ast::ty_u16) }
+              "u32" { repr_int(ast::ty_u32) }
+              "u64" { repr_int(ast::ty_u64) }
+              _ { repr }
+            }
+          }
+          _ { repr }
+        };
+    }
+    ret repr;
+}
+
+enum fn_call_conv { fcc_rust, fcc_fastcall, fcc_stdcall, }
+
+// Like native_abi(), but for the `#[abi = "..."]` forms that make sense
+// on an ordinary Rust fn item rather than a `native mod`: picking a
+// non-default calling convention for the declared function itself
+// (Windows interop, matching a specific ABI), not the calling convention
+// used to call out to a foreign symbol. Absence of the attribute, or an
+// unrecognized value, means the default Rust calling convention.
+fn find_fn_call_conv(attrs: [ast::attribute]) -> fn_call_conv {
+    alt attr::get_meta_item_value_str_by_name(attrs, "abi") {
+      option::some("fastcall") { fcc_fastcall }
+      option::some("stdcall") { fcc_stdcall }
+      _ { fcc_rust }
+    }
+}
+
 fn meta_item_from_list(
     items: [@ast::meta_item],
     name: str
@@ -867,9 +867,10 @@ native mod llvm {
     fn LLVMRustCreateMemoryBufferWithContentsOfFile(Path: sbuf) ->
        MemoryBufferRef;
 
-    /* FIXME: The FileType is an enum.*/
+    /* FIXME: The FileType and CodeModel are enums.*/
     fn LLVMRustWriteOutputFile(PM: PassManagerRef, M: ModuleRef, Triple: sbuf,
                                Output: sbuf, FileType: c_int, OptLevel: c_int,
+                               CodeModel: c_int,
                                EnableSegmentedStacks: bool);
 
     /** Returns a string describing the last error caused by an LLVMRust*
@@ -35,6 +35,7 @@ enum ast_tag {
     at_expr_node_cast,
     at_expr_node_if,
     at_expr_node_while,
+    at_expr_node_asm,
 
     at_none,
     at_some,
@@ -395,9 +396,10 @@ impl ast_output for ast_ctxt {
             }
           }
 
-          ast::expr_assert(e) {
+          ast::expr_assert(e, msg) {
             self.tag(at_expr_node_assert) {||
                 self.expr(e);
+                self.opt(msg) {|m| self.expr(m) }
             }
           }
 
@@ -421,6 +423,12 @@ impl ast_output for ast_ctxt {
                 /* todo */
             }
           }
+
+          ast::expr_asm(a) {
+            self.tag(at_expr_node_asm) {||
+                /* todo: cross-crate inlining of asm! exprs */
+            }
+          }
         }
     }
 
@@ -2253,6 +2253,9 @@ fn resolve_impl_in_expr(e: @env, x: @ast::expr, sc: iscopes, v: vt<iscopes>) {
       ast::expr_assign_op(_, _, _) | ast::expr_index(_, _) {
         e.impl_map.insert(x.id, sc);
       }
+      // The sequence of a `for` loop may need impls in scope to resolve
+      // a `next` method, even though the `for` expr itself doesn't.
+      ast::expr_for(_, seq, _) { e.impl_map.insert(seq.id, sc); }
       _ {}
     }
     visit::visit_expr(x, sc, v);
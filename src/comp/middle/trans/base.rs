@@ -185,6 +185,18 @@ fn get_extern_fn(externs: hashmap<str, ValueRef>, llmod: ModuleRef, name: str,
     ret f;
 }
 
+// This always addresses `name` directly (an ordinary LLVMAddGlobal
+// declaration), which is only correct for the static/default relocation
+// model. Making a PIC build route external references like this through
+// the GOT isn't something trans_var/lookup_discriminant/
+// trans_external_path (this function's callers) could do on their own by
+// picking a different way to declare or load the global: real PIC
+// indirection is a TargetMachine-level codegen decision, and this tree
+// has neither a `-relocation-model` session option nor a Reloc parameter
+// on LLVMRustWriteOutputFile (see the CodeModel handling in
+// back::link::write::run_passes for the nearest existing analogue) for trans to
+// consult in the first place. Both would need to land before any of
+// get_extern_const's callers could vary their codegen on it.
 fn get_extern_const(externs: hashmap<str, ValueRef>, llmod: ModuleRef,
                     name: str, ty: TypeRef) -> ValueRef {
     if externs.contains_key(name) { ret externs.get(name); }
@@ -193,6 +205,13 @@ fn get_extern_const(externs: hashmap<str, ValueRef>, llmod: ModuleRef,
     ret c;
 }
 
+// A named external bool the runtime can flip -- shared by check_claims
+// below and any #[runtime_flag] const (see collect_item's item_const arm),
+// which are really just two ways of asking for the same global.
+fn get_runtime_flag_global(ccx: crate_ctxt, name: str) -> ValueRef {
+    get_extern_const(ccx.externs, ccx.llmod, name, T_bool())
+}
+
 fn get_simple_extern_fn(cx: block,
                         externs: hashmap<str, ValueRef>,
                         llmod: ModuleRef,
@@ -243,6 +262,26 @@ fn alloca(cx: block, t: TypeRef) -> ValueRef {
     ret Alloca(raw_block(cx.fcx, cx.fcx.llstaticallocas), t);
 }
 
+// A `stack_alloc::<T>(n: uint) -> *T` rust-intrinsic wrapping this would
+// be a natural `unsafe`-only scoped-scratch-buffer primitive, freed at
+// function exit the way alloc_ty's own dynastack_alloca use already is.
+// It can't be wired through collect_native_item's
+// ast::native_abi_rust_intrinsic case the way get_type_desc/memmove/
+// clflush are, though: every existing rust-intrinsic there is declared
+// as an ordinary extern fn whose body lives as a self-contained,
+// hand-written LLVM IR function in src/rt/intrinsics/intrinsics.*.ll.in
+// (see e.g. rust_intrinsic_get_type_desc there) -- a leaf function with
+// no access to the *calling* function's own fn_ctxt. dynastack_alloca,
+// by contrast, is only well-formed emitted directly into the caller: it
+// creates the caller's llobstacktoken on first use (mk_obstack_token,
+// below) and must run in the caller's llderivedtydescs/
+// lldynamicallocas blocks so it dominates the caller's own dynastack
+// frees at cleanup time (search llobstacktoken in this file). Exposing
+// it as a name trans_call special-cases at the call site (the way
+// is_unpredictable_callee tags a call by its callee's path, below)
+// rather than as a real rust-intrinsic extern fn is the shape this would
+// need; that's a distinct, larger change than adding one more .ll leaf
+// function, so it's not done here.
 fn dynastack_alloca(cx: block, t: TypeRef, n: ValueRef, ty: ty::t) ->
    ValueRef {
     if cx.unreachable { ret llvm::LLVMGetUndef(T_ptr(t)); }
@@ -292,6 +331,28 @@ fn bump_ptr(bcx: block, t: ty::t, base: ValueRef, sz: ValueRef) ->
     } else { bumped }
 }
 
+// Looks up the size/align of a dynamically-sized type in the fn_ctxt's
+// per-function cache, computing and caching it in the derived-tydesc block
+// (so it dominates every later use) the first time it's asked for. Used by
+// GEP_tup_like's compute_off, which otherwise re-emits the same
+// tydesc-driven size_of/align_of IR every time the same field is passed
+// over on the way to a later one.
+fn memoized_size_align(cx: block, t: ty::t) -> {bcx: block, sz: ValueRef,
+                                                align: ValueRef} {
+    alt cx.fcx.size_align_metrics.find(t) {
+      some(m) { ret {bcx: cx, sz: m.sz, align: m.align}; }
+      none {/* fall through */}
+    }
+    let bcx = raw_block(cx.fcx, cx.fcx.llderivedtydescs);
+    let sz = size_of(bcx, t);
+    bcx = sz.bcx;
+    let align = align_of(bcx, t);
+    bcx = align.bcx;
+    cx.fcx.llderivedtydescs = bcx.llbb;
+    cx.fcx.size_align_metrics.insert(t, {sz: sz.val, align: align.val});
+    ret {bcx: cx, sz: sz.val, align: align.val};
+}
+
 // Replacement for the LLVM 'GEP' instruction when field-indexing into a
 // tuple-like structure (tup, rec) with a static index. This one is driven off
 // ty::struct and knows what to do when it runs into a ty_param stuck in the
@@ -312,18 +373,16 @@ fn GEP_tup_like(bcx: block, t: ty::t, base: ValueRef, ixs: [int])
         let bcx = bcx, off = off;
         int::range(0, ix) {|i|
             let comp_t = ty::get_element_type(t, i as uint);
-            let align = align_of(bcx, comp_t);
-            bcx = align.bcx;
-            off = align_to(bcx, off, align.val);
-            let sz = size_of(bcx, comp_t);
-            bcx = sz.bcx;
-            off = Add(bcx, off, sz.val);
+            let m = memoized_size_align(bcx, comp_t);
+            bcx = m.bcx;
+            off = align_to(bcx, off, m.align);
+            off = Add(bcx, off, m.sz);
         }
 
         let comp_t = ty::get_element_type(t, ix as uint);
-        let align = align_of(bcx, comp_t);
-        bcx = align.bcx;
-        off = align_to(bcx, off, align.val);
+        let m = memoized_size_align(bcx, comp_t);
+        bcx = m.bcx;
+        off = align_to(bcx, off, m.align);
 
         be compute_off(bcx, off, comp_t, ixs, n+1u);
     }
@@ -400,10 +459,34 @@ fn GEP_enum(cx: block, llblobptr: ValueRef, enum_id: ast::def_id,
     ret rslt(rs.bcx, val);
 }
 
+// trans_heap_profile: if the compiler was invoked with --heap-profile,
+// report the allocation site (approximated by the innermost enclosing
+// span) and size to the heap_profile upcall. A no-op, emitting no
+// instructions, when the flag is off.
+fn trans_heap_profile(cx: block, llsize: ValueRef) {
+    if !cx.ccx().sess.opts.heap_profile { ret; }
+    let ccx = cx.ccx();
+    let V_filename;
+    let V_line;
+    alt cx.block_span {
+      some(sp) {
+        let loc = codemap::lookup_char_pos(cx.sess().parse_sess.cm, sp.lo);
+        V_filename = C_cstr(ccx, loc.file.name);
+        V_line = loc.line as int;
+      }
+      none { V_filename = C_cstr(ccx, "<runtime>"); V_line = 0; }
+    }
+    let V_filename = PointerCast(cx, V_filename, T_ptr(T_i8()));
+    let V_size = ZExtOrBitCast(cx, llsize, T_size_t(ccx.sess.targ_cfg));
+    Call(cx, ccx.upcalls.heap_profile,
+         [V_filename, C_int(ccx, V_line), V_size]);
+}
+
 // trans_shared_malloc: expects a type indicating which pointer type we want
 // and a size indicating how much space we want malloc'd.
 fn trans_shared_malloc(cx: block, llptr_ty: TypeRef, llsize: ValueRef)
    -> result {
+    trans_heap_profile(cx, llsize);
     let rval = Call(cx, cx.ccx().upcalls.shared_malloc, [llsize]);
     ret rslt(cx, PointerCast(cx, rval, llptr_ty));
 }
@@ -442,6 +525,12 @@ fn trans_malloc_boxed_raw(bcx: block, t: ty::t,
     let {bcx, val: lltydesc} = get_tydesc(bcx, t, true, static_ti);
     lazily_emit_all_tydesc_glue(ccx, static_ti);
 
+    if bcx.ccx().sess.opts.heap_profile {
+        let llsize = Load(bcx, GEPi(bcx, lltydesc,
+                                    [0, abi::tydesc_field_size]));
+        trans_heap_profile(bcx, llsize);
+    }
+
     // Allocate space:
     let rval = Call(bcx, ccx.upcalls.malloc, [lltydesc]);
     ret rslt(bcx, PointerCast(bcx, rval, llty));
@@ -491,7 +580,8 @@ fn linearize_ty_params(cx: block, t: ty::t) ->
 }
 
 fn trans_stack_local_derived_tydesc(cx: block, llsz: ValueRef,
-                                    llalign: ValueRef, llroottydesc: ValueRef,
+                                    llalign: ValueRef, llprefalign: ValueRef,
+                                    llroottydesc: ValueRef,
                                     llfirstparam: ValueRef, n_params: uint)
     -> ValueRef {
     let llmyroottydesc = alloca(cx, cx.ccx().tydesc_type);
@@ -510,6 +600,9 @@ fn trans_stack_local_derived_tydesc(cx: block, llsz: ValueRef,
                    [0, abi::tydesc_field_size]);
     store_inbounds(cx, llalign, llmyroottydesc,
                    [0, abi::tydesc_field_align]);
+    // The slot is typed as a glue pointer.
+    store_inbounds(cx, IntToPtr(cx, llprefalign, T_ptr(T_glue_fn(ccx))),
+                   llmyroottydesc, [0, abi::tydesc_field_pref_align]);
     // FIXME legacy field, can be dropped
     store_inbounds(cx, C_uint(ccx, 0u), llmyroottydesc,
                    [0, abi::tydesc_field_obj_params]);
@@ -540,6 +633,8 @@ fn get_derived_tydesc(cx: block, t: ty::t, escapes: bool,
     bcx = sz.bcx;
     let align = align_of(bcx, t);
     bcx = align.bcx;
+    let pref_align = pref_align_of(bcx, t);
+    bcx = pref_align.bcx;
 
     // Store the captured type descriptors in an alloca if the caller isn't
     // promising to do so itself.
@@ -571,6 +666,15 @@ fn get_derived_tydesc(cx: block, t: ty::t, escapes: bool,
     let v;
     if escapes {
         let ccx = bcx.ccx();
+        // upcall_get_type_desc's signature has no align/pref_align split
+        // (see rust_upcall.cpp) -- like every other field it doesn't take
+        // explicitly (the glue pointers, shape, ...), rust_crate_cache::
+        // get_type_desc memcpy's the root descriptor first and only
+        // overwrites size/align on top, so an escaping tydesc's pref_align
+        // is the *root* type's pref_align rather than one recomputed for
+        // this exact instantiation. Good enough for a debugging/tuning
+        // value; extending the upcall for full precision here is out of
+        // scope.
         let td_val =
             Call(bcx, ccx.upcalls.get_type_desc,
                  [C_null(T_ptr(T_nil())), sz.val,
@@ -578,7 +682,8 @@ fn get_derived_tydesc(cx: block, t: ty::t, escapes: bool,
                   C_uint(ccx, 0u)]);
         v = td_val;
     } else {
-        v = trans_stack_local_derived_tydesc(bcx, sz.val, align.val, root,
+        v = trans_stack_local_derived_tydesc(bcx, sz.val, align.val,
+                                             pref_align.val, root,
                                              llfirstparam, n_params);
     }
     bcx.fcx.derived_tydescs.insert(t, {lltydesc: v, escapes: escapes});
@@ -639,6 +744,94 @@ fn set_always_inline(f: ValueRef) {
                               0u as c_uint);
 }
 
+// set_always_inline/set_no_inline above put the attribute on a function's
+// own definition, applying to every call to it. These put it on a single
+// Call/Invoke instruction instead -- LLVM's per-call-site counterpart,
+// via an index of ~0u (all-ones), the sentinel this LLVM version's
+// attribute-index convention uses for "the call/function itself" as
+// opposed to a specific argument (0 would be the return value, 1..N the
+// arguments). See inline_decision_for_call for why a call site, rather
+// than the callee's definition, is what gets marked here.
+const call_site_attr_index: c_uint = 0xffffffffu as c_uint;
+
+fn set_call_always_inline(call: ValueRef) {
+    llvm::LLVMAddInstrAttribute(call, call_site_attr_index,
+                                lib::llvm::AlwaysInlineAttribute as c_uint);
+}
+
+fn set_call_no_inline(call: ValueRef) {
+    llvm::LLVMAddInstrAttribute(call, call_site_attr_index,
+                                lib::llvm::NoInlineAttribute as c_uint);
+}
+
+// A hint, unlike set_always_inline: LLVM is free to ignore it. This is
+// what a bare #[inline] (as opposed to #[inline(always)]) should apply.
+fn set_inline_hint(f: ValueRef) {
+    llvm::LLVMAddFunctionAttr(f, lib::llvm::InlineHintAttribute as c_uint,
+                              0u as c_uint);
+}
+
+// #[instruction_set(arm)]/#[instruction_set(thumb)] asks for a
+// per-function override of the module-wide instruction set trans_crate
+// picks via the target triple, so a single module can mix ARM and Thumb
+// (e.g. an interrupt handler that must stay in ARM mode next to Thumb
+// code). Real support needs LLVM's target-dependent function attributes
+// (what a modern "target-features"="+thumb-mode" string attribute would
+// carry), but the LLVM C API bound in lib::llvm here only exposes the
+// older fixed enum-based LLVMAddFunctionAttr, which has no per-function
+// instruction-set slot -- so there's nothing to actually set on `f` yet.
+fn set_instruction_set(ccx: crate_ctxt, sp: span, _f: ValueRef,
+                       iset: attr::instruction_set) {
+    alt iset {
+      attr::is_none { }
+      attr::is_arm | attr::is_thumb {
+        if ccx.sess.targ_cfg.arch != session::arch_arm {
+            ccx.sess.span_fatal(sp, "#[instruction_set] is only supported \
+                                     on arm targets");
+        }
+        ccx.sess.span_unimpl(sp, "#[instruction_set]: this LLVM binding \
+            has no target-dependent function attribute to carry a \
+            per-function instruction-set override");
+      }
+    }
+}
+
+// #[ifunc] asks for `f` to be emitted as a GNU indirect function: instead
+// of `f` itself being called, the dynamic linker calls it once at load
+// time and installs whatever function pointer it returns as the real
+// symbol, letting e.g. a SIMD library pick an AVX or scalar
+// implementation without the caller branching on CPU features. Real
+// support needs LLVM's GlobalIFunc (or, failing that, hand-emitted
+// `.type foo, @gnu_indirect_function` assembly) -- the LLVM C API bound
+// in lib::llvm here only exposes LLVMAddAlias, which makes an ordinary
+// alias with no ifunc resolver semantics, so there's no way to actually
+// mark `f` as an ifunc yet.
+fn set_ifunc(ccx: crate_ctxt, sp: span, _f: ValueRef) {
+    ccx.sess.span_unimpl(sp, "#[ifunc]: this LLVM binding has no ifunc \
+        (GNU indirect function) construct to emit `f` as -- LLVMAddAlias \
+        only produces an ordinary alias, not an ifunc resolver");
+}
+
+// Tell LLVM this function (typically a setjmp-like native declaration)
+// may return more than once, so it can't make the usual single-return
+// assumptions across calls to it.
+fn set_returns_twice(f: ValueRef) {
+    llvm::LLVMAddFunctionAttr(f, lib::llvm::ReturnsTwiceAttribute as c_uint,
+                              0u as c_uint);
+}
+
+// Tells LLVM `f` never unwinds, so calls to it can be emitted as plain
+// `call`s (no landing pad needed on their account) and, if `f` unwinds
+// anyway (e.g. a stray C++ exception crossing back into Rust), that's UB
+// LLVM is free to miscompile around -- in practice that means the unwind
+// hits a `call` with no handler and the personality routine's default
+// behaviour (an abort) takes over, rather than propagating into Rust
+// frames that never expected to see one.
+fn set_nounwind(f: ValueRef) {
+    llvm::LLVMAddFunctionAttr(f, lib::llvm::NoUnwindAttribute as c_uint,
+                              0u as c_uint);
+}
+
 fn set_custom_stack_growth_fn(f: ValueRef) {
     // FIXME: Remove this hack to work around the lack of u64 in the FFI.
     llvm::LLVMAddFunctionAttr(f, 0u as c_uint, 1u as c_uint);
@@ -657,19 +850,22 @@ fn declare_tydesc(ccx: crate_ctxt, t: ty::t, ty_params: [uint])
     log(debug, "+++ declare_tydesc " + ty_to_str(ccx.tcx, t));
     let llsize;
     let llalign;
+    let llprefalign;
     if check type_has_static_size(ccx, t) {
         let llty = type_of(ccx, t);
         llsize = llsize_of(ccx, llty);
         llalign = llalign_of(ccx, llty);
+        llprefalign = llalign_of_pref(ccx, llty);
     } else {
         // These will be overwritten as the derived tydesc is generated, so
         // we create placeholder values.
 
         llsize = C_int(ccx, 0);
         llalign = C_int(ccx, 0);
+        llprefalign = C_int(ccx, 0);
     }
     let name;
-    if ccx.sess.opts.debuginfo {
+    if ccx.sess.opts.debuginfo >= 1u {
         name = mangle_internal_name_by_type_only(ccx, t, "tydesc");
         name = sanitize(name);
     } else { name = mangle_internal_name_by_seq(ccx, "tydesc"); }
@@ -681,9 +877,12 @@ fn declare_tydesc(ccx: crate_ctxt, t: ty::t, ty_params: [uint])
           tydesc: gvar,
           size: llsize,
           align: llalign,
+          pref_align: llprefalign,
+          needs_drop: ty::type_needs_drop(ccx.tcx, t),
           mutable take_glue: none,
           mutable drop_glue: none,
           mutable free_glue: none,
+          mutable cmp_glue: none,
           ty_params: ty_params};
     log(debug, "--- declare_tydesc " + ty_to_str(ccx.tcx, t));
     ret info;
@@ -695,7 +894,11 @@ fn declare_generic_glue(ccx: crate_ctxt, t: ty::t, llfnty: TypeRef,
                         name: str) -> ValueRef {
     let name = name;
     let fn_nm;
-    if ccx.sess.opts.debuginfo {
+    // -shared-glue also needs a type-derived name: two translation units
+    // (or two monomorphizations in the same one) only fold their glue
+    // together at link time if they emit the exact same symbol, and a
+    // seq-based name depends on emission order, which differs per unit.
+    if ccx.sess.opts.debuginfo >= 1u || ccx.sess.opts.shared_glue {
         fn_nm = mangle_internal_name_by_type_only(ccx, t, "glue_" + name);
         fn_nm = sanitize(fn_nm);
     } else { fn_nm = mangle_internal_name_by_seq(ccx, "glue_" + name); }
@@ -708,7 +911,18 @@ fn make_generic_glue_inner(ccx: crate_ctxt, t: ty::t,
                            llfn: ValueRef, helper: glue_helper,
                            ty_params: [uint]) -> ValueRef {
     let fcx = new_fn_ctxt(ccx, [], llfn, none);
-    lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
+    // Ordinarily each crate (and each monomorphization within it) keeps
+    // its own private copy of a piece of glue, since nothing else can
+    // possibly reference the internal symbol. With -shared-glue, glue for
+    // the same type is instead emitted with linkonce_odr linkage under
+    // the deterministic name declare_generic_glue gives it, so identical
+    // definitions across translation units collapse into one at link
+    // time instead of each shipping its own copy.
+    lib::llvm::SetLinkage(llfn, if ccx.sess.opts.shared_glue {
+        lib::llvm::LinkOnceODRLinkage
+    } else {
+        lib::llvm::InternalLinkage
+    });
     ccx.stats.n_glues_created += 1u;
     // Any nontrivial glue is with values passed *by alias*; this is a
     // requirement since in many contexts glue is invoked indirectly and
@@ -758,23 +972,60 @@ fn make_generic_glue(ccx: crate_ctxt, t: ty::t, llfn: ValueRef,
 }
 
 fn emit_tydescs(ccx: crate_ctxt) {
+    // Monomorphization often produces many tydescs that are structurally
+    // identical (same size, align, glue functions, and shape) but were
+    // declared for different ty::t's. `seen` maps that structural
+    // signature to the first tydesc global we emitted for it, so later
+    // duplicates get folded onto it and their now-unused globals dropped,
+    // the same way get_shape_glue folds identical glue functions.
+    let seen = new_str_hash::<ValueRef>();
     ccx.tydescs.items {|key, val|
         let glue_fn_ty = T_ptr(T_glue_fn(ccx));
         let ti = val;
+        // A type that's statically known to need no take/drop/free glue
+        // (see declare_tydesc) never has lazily_emit_tydesc_glue asked to
+        // create any -- its slots stay null here without ever having
+        // declared a function that would just fall through to the `_ {
+        // bcx }`/`_ { true }`-guarded no-op arms in
+        // make_take_glue/make_drop_glue/make_free_glue.
         let take_glue =
-            alt ti.take_glue {
-              none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
-              some(v) { ccx.stats.n_real_glues += 1u; v }
+            if !ti.needs_drop { ccx.stats.n_glues_elided += 1u;
+                                C_null(glue_fn_ty) }
+            else {
+                alt ti.take_glue {
+                  none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
+                  some(v) { ccx.stats.n_real_glues += 1u; v }
+                }
             };
         let drop_glue =
-            alt ti.drop_glue {
-              none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
-              some(v) { ccx.stats.n_real_glues += 1u; v }
+            if !ti.needs_drop { ccx.stats.n_glues_elided += 1u;
+                                C_null(glue_fn_ty) }
+            else {
+                alt ti.drop_glue {
+                  none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
+                  some(v) { ccx.stats.n_real_glues += 1u; v }
+                }
             };
         let free_glue =
-            alt ti.free_glue {
-              none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
-              some(v) { ccx.stats.n_real_glues += 1u; v }
+            if !ti.needs_drop { ccx.stats.n_glues_elided += 1u;
+                                C_null(glue_fn_ty) }
+            else {
+                alt ti.free_glue {
+                  none { ccx.stats.n_null_glues += 1u; C_null(glue_fn_ty) }
+                  some(v) { ccx.stats.n_real_glues += 1u; v }
+                }
+            };
+        // Comparison glue is only ever created (by lazily_emit_cmp_glue)
+        // for a type that's actually compared somewhere in the crate; a
+        // type that's only ever moved or dropped keeps a null cmp_glue
+        // slot instead of paying for glue it will never use.
+        let cmp_glue =
+            alt ti.cmp_glue {
+              none { ccx.stats.n_null_glues += 1u; C_null(T_ptr(T_i8())) }
+              some(v) {
+                ccx.stats.n_real_glues += 1u;
+                llvm::LLVMConstPointerCast(v, T_ptr(T_i8()))
+              }
             };
 
         let shape = shape_of(ccx, key, ti.ty_params);
@@ -782,6 +1033,14 @@ fn emit_tydescs(ccx: crate_ctxt) {
             llvm::LLVMConstPointerCast(ccx.shape_cx.llshapetables,
                                        T_ptr(T_i8()));
 
+        // A non-null sentinel here is enough for libcore::sys::needs_drop to
+        // tell the two cases apart at runtime -- nothing ever dereferences
+        // this slot as a real glue pointer, so any non-null value would do.
+        let needs_drop_flag =
+            if ti.needs_drop {
+                llvm::LLVMConstIntToPtr(C_int(ccx, 1), T_ptr(T_i8()))
+            } else { C_null(T_ptr(T_i8())) };
+
         let tydesc =
             C_named_struct(ccx.tydesc_type,
                            [C_null(T_ptr(T_ptr(ccx.tydesc_type))),
@@ -790,20 +1049,56 @@ fn emit_tydescs(ccx: crate_ctxt) {
                             take_glue, // take_glue
                             drop_glue, // drop_glue
                             free_glue, // free_glue
-                            C_null(T_ptr(T_i8())), // unused
+                            needs_drop_flag, // needs_drop
                             C_null(glue_fn_ty), // sever_glue
                             C_null(glue_fn_ty), // mark_glue
-                            C_null(glue_fn_ty), // unused
-                            C_null(T_ptr(T_i8())), // cmp_glue
+                            // The slot is typed as a glue pointer.
+                            llvm::LLVMConstIntToPtr(ti.pref_align,
+                                                    glue_fn_ty), // pref_align
+                            cmp_glue, // cmp_glue
                             C_shape(ccx, shape), // shape
                             shape_tables, // shape_tables
                             C_int(ccx, 0), // n_params
                             C_int(ccx, 0)]); // n_obj_params
 
+        // Constants of a given value are uniqued by LLVM, and glue
+        // functions of a given shape are already deduped by get_shape_glue
+        // (see lazily_emit_tydesc_glue above), so a null/named-symbol
+        // identity string for each glue slot plus the size/align/shape is
+        // enough to recognize two tydescs as truly interchangeable.
+        let glue_ident = fn@(v: ValueRef) -> str {
+            unsafe { str::from_cstr(llvm::LLVMGetValueName(v)) }
+        };
+        // cmp_glue is a bitcast of the shared cmp glue function rather
+        // than a bare glue_fn_ty value, so its identity is read off the
+        // underlying function in ti.cmp_glue (there's only ever one)
+        // instead of passing the bitcast itself to glue_ident, which
+        // expects a GlobalValue.
+        let cmp_glue_ident =
+            alt ti.cmp_glue { none { "" } some(v) { glue_ident(v) } };
+        let dedup_key =
+            int::str(llvm::LLVMConstIntGetZExtValue(ti.size) as int) + "," +
+            int::str(llvm::LLVMConstIntGetZExtValue(ti.align) as int) + "," +
+            glue_ident(take_glue) + "," +
+            glue_ident(drop_glue) + "," +
+            glue_ident(free_glue) + "," +
+            cmp_glue_ident + "," +
+            str::from_bytes(shape);
+
         let gvar = ti.tydesc;
-        llvm::LLVMSetInitializer(gvar, tydesc);
-        llvm::LLVMSetGlobalConstant(gvar, True);
-        lib::llvm::SetLinkage(gvar, lib::llvm::InternalLinkage);
+        alt seen.find(dedup_key) {
+          some(canonical) {
+            ccx.stats.n_tydescs_deduped += 1u;
+            llvm::LLVMReplaceAllUsesWith(gvar, canonical);
+            llvm::LLVMDeleteGlobal(gvar);
+          }
+          none {
+            llvm::LLVMSetInitializer(gvar, tydesc);
+            llvm::LLVMSetGlobalConstant(gvar, True);
+            lib::llvm::SetLinkage(gvar, lib::llvm::InternalLinkage);
+            seen.insert(dedup_key, gvar);
+          }
+        }
     };
 }
 
@@ -877,8 +1172,8 @@ fn make_free_glue(bcx: block, v: ValueRef, t: ty::t) {
         let v = PointerCast(bcx, v, type_of(ccx, t));
         let td = Load(bcx, GEPi(bcx, v, [0, abi::box_field_tydesc]));
         let valptr = GEPi(bcx, v, [0, abi::box_field_body]);
-        call_tydesc_glue_full(bcx, valptr, td, abi::tydesc_field_drop_glue,
-                              none);
+        let bcx = call_tydesc_glue_full(bcx, valptr, td,
+                                        abi::tydesc_field_drop_glue, none);
         trans_free(bcx, v)
       }
       ty::ty_uniq(content_mt) {
@@ -1163,6 +1458,14 @@ fn iter_structural_ty(cx: block, av: ValueRef, t: ty::t,
         ret f(bcx, llfld_a, inner1);
       }
       ty::ty_enum(tid, tps) {
+        // This switch-on-discriminant shape is what any discriminated
+        // union (including a small-vector's inline-vs-heap tag) rides
+        // on: type_of/GEP_tup_like size the union for the largest
+        // variant, and this loop below only walks into the variant
+        // selected by the live discriminant, so a variant that owns no
+        // resources (e.g. an inline small-vector payload) is never
+        // touched when the heap variant is the live one, and vice
+        // versa.
         let variants = ty::enum_variants(cx.tcx(), tid);
         let n_variants = (*variants).len();
 
@@ -1209,6 +1512,59 @@ fn lazily_emit_all_tydesc_glue(ccx: crate_ctxt,
     lazily_emit_tydesc_glue(ccx, abi::tydesc_field_free_glue, static_ti);
 }
 
+// Builds the one-and-only comparison glue trampoline for this crate. Every
+// type's cmp glue body is identical (it just forwards to upcalls.cmp_type,
+// the same sequence call_cmp_glue used to inline at every comparison site),
+// so unlike make_take_glue/make_drop_glue/make_free_glue there's nothing
+// type-specific to generate: this is here purely so a type's tydesc can
+// point at a real function only once that type is actually compared,
+// instead of every tydesc unconditionally carrying comparison support it
+// may never use.
+fn make_cmp_glue(ccx: crate_ctxt) -> ValueRef {
+    let llfn = decl_cdecl_fn(ccx.llmod, "cmp_glue", T_cmp_glue_fn(ccx));
+    lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
+    set_always_inline(llfn);
+    ccx.stats.n_glues_created += 1u;
+
+    let fcx = new_fn_ctxt(ccx, [], llfn, none);
+    let bcx = top_scope_block(fcx, none);
+    let lltop = bcx.llbb;
+    let llresultptr = llvm::LLVMGetParam(llfn, 0u as c_uint);
+    let lltydesc = llvm::LLVMGetParam(llfn, 1u as c_uint);
+    let lltydescs = llvm::LLVMGetParam(llfn, 2u as c_uint);
+    let lllhs = llvm::LLVMGetParam(llfn, 3u as c_uint);
+    let llrhs = llvm::LLVMGetParam(llfn, 4u as c_uint);
+    let llop = llvm::LLVMGetParam(llfn, 5u as c_uint);
+    Call(bcx, bcx.ccx().upcalls.cmp_type,
+         [llresultptr, lltydesc, lltydescs, lllhs, llrhs, llop]);
+    finish_fn(fcx, lltop);
+    ret llfn;
+}
+
+fn lazily_emit_cmp_glue(ccx: crate_ctxt, static_ti: option<@tydesc_info>) {
+    alt static_ti {
+      none { }
+      some(ti) {
+        alt ti.cmp_glue {
+          some(_) { }
+          none {
+            #debug("+++ lazily_emit_cmp_glue %s", ty_to_str(ccx.tcx, ti.ty));
+            let glue = alt ccx.cmp_glue {
+              some(v) { v }
+              none {
+                let v = make_cmp_glue(ccx);
+                ccx.cmp_glue = some(v);
+                v
+              }
+            };
+            ti.cmp_glue = some(glue);
+            #debug("--- lazily_emit_cmp_glue %s", ty_to_str(ccx.tcx, ti.ty));
+          }
+        }
+      }
+    }
+}
+
 fn lazily_emit_all_generic_info_tydesc_glues(ccx: crate_ctxt,
                                              gi: generic_info) {
     for ti: option<@tydesc_info> in gi.static_tis {
@@ -1216,23 +1572,48 @@ fn lazily_emit_all_generic_info_tydesc_glues(ccx: crate_ctxt,
     }
 }
 
+// Take/drop/free glue only depends on a type's shape, not its identity,
+// so structurally-identical types (e.g. any two records of two pointers)
+// can share one glue function. `cache` holds the glue already emitted for
+// a given shape (keyed on the raw shape bytes), so repeated shapes just
+// look an existing glue function up instead of declaring and defining a
+// new one.
+fn get_shape_glue(ccx: crate_ctxt, cache: hashmap<str, ValueRef>,
+                  t: ty::t, ty_params: [uint], helper: glue_helper,
+                  name: str) -> ValueRef {
+    let key = str::from_bytes(shape_of(ccx, t, ty_params));
+    alt cache.find(key) {
+      some(glue_fn) {
+        ccx.stats.n_glues_deduped += 1u;
+        glue_fn
+      }
+      none {
+        let glue_fn = declare_generic_glue(ccx, t, T_glue_fn(ccx), name);
+        cache.insert(key, glue_fn);
+        make_generic_glue(ccx, t, glue_fn, helper, ty_params, name);
+        glue_fn
+      }
+    }
+}
+
 fn lazily_emit_tydesc_glue(ccx: crate_ctxt, field: int,
                            static_ti: option<@tydesc_info>) {
     alt static_ti {
       none { }
       some(ti) {
+        // Statically known to need no take/drop/free glue at all (see
+        // declare_tydesc); leave the slot none forever so emit_tydescs
+        // fills it with null instead of a trivial glue function.
+        if !ti.needs_drop { ret; }
         if field == abi::tydesc_field_take_glue {
             alt ti.take_glue {
               some(_) { }
               none {
                 #debug("+++ lazily_emit_tydesc_glue TAKE %s",
                        ty_to_str(ccx.tcx, ti.ty));
-                let glue_fn = declare_generic_glue
-                    (ccx, ti.ty, T_glue_fn(ccx), "take");
-                ti.take_glue = some(glue_fn);
-                make_generic_glue(ccx, ti.ty, glue_fn,
-                                  make_take_glue,
-                                  ti.ty_params, "take");
+                ti.take_glue = some(get_shape_glue(
+                    ccx, ccx.shape_take_glues, ti.ty, ti.ty_params,
+                    make_take_glue, "take"));
                 #debug("--- lazily_emit_tydesc_glue TAKE %s",
                        ty_to_str(ccx.tcx, ti.ty));
               }
@@ -1243,12 +1624,9 @@ fn lazily_emit_tydesc_glue(ccx: crate_ctxt, field: int,
               none {
                 #debug("+++ lazily_emit_tydesc_glue DROP %s",
                        ty_to_str(ccx.tcx, ti.ty));
-                let glue_fn =
-                    declare_generic_glue(ccx, ti.ty, T_glue_fn(ccx), "drop");
-                ti.drop_glue = some(glue_fn);
-                make_generic_glue(ccx, ti.ty, glue_fn,
-                                  make_drop_glue,
-                                  ti.ty_params, "drop");
+                ti.drop_glue = some(get_shape_glue(
+                    ccx, ccx.shape_drop_glues, ti.ty, ti.ty_params,
+                    make_drop_glue, "drop"));
                 #debug("--- lazily_emit_tydesc_glue DROP %s",
                        ty_to_str(ccx.tcx, ti.ty));
               }
@@ -1259,12 +1637,9 @@ fn lazily_emit_tydesc_glue(ccx: crate_ctxt, field: int,
               none {
                 #debug("+++ lazily_emit_tydesc_glue FREE %s",
                        ty_to_str(ccx.tcx, ti.ty));
-                let glue_fn =
-                    declare_generic_glue(ccx, ti.ty, T_glue_fn(ccx), "free");
-                ti.free_glue = some(glue_fn);
-                make_generic_glue(ccx, ti.ty, glue_fn,
-                                  make_free_glue,
-                                  ti.ty_params, "free");
+                ti.free_glue = some(get_shape_glue(
+                    ccx, ccx.shape_free_glues, ti.ty, ti.ty_params,
+                    make_free_glue, "free"));
                 #debug("--- lazily_emit_tydesc_glue FREE %s",
                        ty_to_str(ccx.tcx, ti.ty));
               }
@@ -1275,7 +1650,8 @@ fn lazily_emit_tydesc_glue(ccx: crate_ctxt, field: int,
 }
 
 fn call_tydesc_glue_full(cx: block, v: ValueRef, tydesc: ValueRef,
-                         field: int, static_ti: option<@tydesc_info>) {
+                         field: int, static_ti: option<@tydesc_info>)
+    -> block {
     lazily_emit_tydesc_glue(cx.ccx(), field, static_ti);
 
     let static_glue_fn = none;
@@ -1306,16 +1682,25 @@ fn call_tydesc_glue_full(cx: block, v: ValueRef, tydesc: ValueRef,
       some(sgf) { llfn = sgf; }
     }
 
-    Call(cx, llfn, [C_null(T_ptr(T_nil())), C_null(T_ptr(T_nil())),
-                    lltydescs, llrawptr]);
+    // A glue slot loaded straight out of a runtime tydesc (rather than a
+    // known static_glue_fn) may belong to a type that turned out to need
+    // no take/drop/free glue (see declare_tydesc/emit_tydescs), in which
+    // case llfn is a null function pointer; guard the call so this path
+    // stays safe for the type-parameter case where the concrete type
+    // isn't known until runtime.
+    ret with_cond(cx, IsNotNull(cx, llfn)) {|bcx|
+        Call(bcx, llfn, [C_null(T_ptr(T_nil())), C_null(T_ptr(T_nil())),
+                        lltydescs, llrawptr]);
+        bcx
+    };
 }
 
 fn call_tydesc_glue(cx: block, v: ValueRef, t: ty::t, field: int) ->
    block {
+    if !ty::type_needs_drop(cx.tcx(), t) { ret cx; }
     let ti: option<@tydesc_info> = none::<@tydesc_info>;
     let {bcx: bcx, val: td} = get_tydesc(cx, t, false, ti);
-    call_tydesc_glue_full(bcx, v, td, field, ti);
-    ret bcx;
+    ret call_tydesc_glue_full(bcx, v, td, field, ti);
 }
 
 fn call_cmp_glue(cx: block, lhs: ValueRef, rhs: ValueRef, t: ty::t,
@@ -1334,7 +1719,9 @@ fn call_cmp_glue(cx: block, lhs: ValueRef, rhs: ValueRef, t: ty::t,
 
     let llrawlhsptr = BitCast(bcx, lllhs, T_ptr(T_i8()));
     let llrawrhsptr = BitCast(bcx, llrhs, T_ptr(T_i8()));
-    r = get_tydesc_simple(bcx, t, false);
+    let ti: option<@tydesc_info> = none::<@tydesc_info>;
+    r = get_tydesc(bcx, t, false, ti);
+    lazily_emit_cmp_glue(bcx.ccx(), ti);
     let lltydesc = r.val;
     bcx = r.bcx;
     let lltydescs =
@@ -1394,12 +1781,7 @@ fn free_ty(cx: block, v: ValueRef, t: ty::t) -> block {
 }
 
 fn call_memmove(cx: block, dst: ValueRef, src: ValueRef,
-                n_bytes: ValueRef) -> result {
-    // FIXME: Provide LLVM with better alignment information when the
-    // alignment is statically known (it must be nothing more than a constant
-    // int, or LLVM complains -- not even a constant element of a tydesc
-    // works).
-
+                n_bytes: ValueRef, align: uint) -> result {
     let ccx = cx.ccx();
     let key = alt ccx.sess.targ_cfg.arch {
       session::arch_x86 | session::arch_arm { "llvm.memmove.p0i8.p0i8.i32" }
@@ -1411,27 +1793,69 @@ fn call_memmove(cx: block, dst: ValueRef, src: ValueRef,
     let src_ptr = PointerCast(cx, src, T_ptr(T_i8()));
     let dst_ptr = PointerCast(cx, dst, T_ptr(T_i8()));
     let size = IntCast(cx, n_bytes, ccx.int_type);
-    let align = C_i32(1i32);
+    let align = C_i32(align as i32);
     let volatile = C_bool(false);
     let ret_val = Call(cx, memmove, [dst_ptr, src_ptr, size,
                                      align, volatile]);
     ret rslt(cx, ret_val);
 }
 
+fn call_memcpy(cx: block, dst: ValueRef, src: ValueRef,
+               n_bytes: ValueRef, align: uint) -> result {
+    // Like call_memmove, but for callers that can prove dst and src
+    // don't overlap (e.g. copying into freshly allocated memory).
+    let ccx = cx.ccx();
+    let key = alt ccx.sess.targ_cfg.arch {
+      session::arch_x86 | session::arch_arm { "llvm.memcpy.p0i8.p0i8.i32" }
+      session::arch_x86_64 { "llvm.memcpy.p0i8.p0i8.i64" }
+    };
+    let i = ccx.intrinsics;
+    assert (i.contains_key(key));
+    let memcpy = i.get(key);
+    let src_ptr = PointerCast(cx, src, T_ptr(T_i8()));
+    let dst_ptr = PointerCast(cx, dst, T_ptr(T_i8()));
+    let size = IntCast(cx, n_bytes, ccx.int_type);
+    let align = C_i32(align as i32);
+    let volatile = C_bool(false);
+    let ret_val = Call(cx, memcpy, [dst_ptr, src_ptr, size,
+                                    align, volatile]);
+    ret rslt(cx, ret_val);
+}
+
 fn memmove_ty(bcx: block, dst: ValueRef, src: ValueRef, t: ty::t) ->
     block {
     let ccx = bcx.ccx();
     if check type_has_static_size(ccx, t) {
         if ty::type_is_structural(t) {
-            let llsz = llsize_of(ccx, type_of(ccx, t));
-            ret call_memmove(bcx, dst, src, llsz).bcx;
+            let llty = type_of(ccx, t);
+            let llsz = llsize_of(ccx, llty);
+            ret call_memmove(bcx, dst, src, llsz, llalign_of_real(ccx, llty)).bcx;
         }
         Store(bcx, Load(bcx, src), dst);
         ret bcx;
     }
 
     let {bcx, val: llsz} = size_of(bcx, t);
-    ret call_memmove(bcx, dst, src, llsz).bcx;
+    ret call_memmove(bcx, dst, src, llsz, 1u).bcx;
+}
+
+// Like memmove_ty, but for copies into memory the caller knows was just
+// allocated and so cannot overlap with src.
+fn memcpy_ty(bcx: block, dst: ValueRef, src: ValueRef, t: ty::t) ->
+    block {
+    let ccx = bcx.ccx();
+    if check type_has_static_size(ccx, t) {
+        if ty::type_is_structural(t) {
+            let llty = type_of(ccx, t);
+            let llsz = llsize_of(ccx, llty);
+            ret call_memcpy(bcx, dst, src, llsz, llalign_of_real(ccx, llty)).bcx;
+        }
+        Store(bcx, Load(bcx, src), dst);
+        ret bcx;
+    }
+
+    let {bcx, val: llsz} = size_of(bcx, t);
+    ret call_memcpy(bcx, dst, src, llsz, 1u).bcx;
 }
 
 enum copy_action { INIT, DROP_EXISTING, }
@@ -1445,6 +1869,16 @@ fn type_is_structural_or_param(t: ty::t) -> bool {
     }
 }
 
+// The `DROP_EXISTING` self-copy guard only fires when `dst` might already
+// hold a live value that could alias `src` (e.g. `x = y` for a structural
+// or unique `x`), since dropping it first and then discovering src ==
+// (old) dst would double-drop. Callers that pass `action == INIT` skip the
+// guard automatically, because there's nothing at `dst` yet to alias
+// against -- this is why trans_rec's base-field copies (freshly GEP'd
+// slots in a record that's still under construction) already use INIT
+// instead of DROP_EXISTING. A caller that legitimately needs
+// DROP_EXISTING semantics but can prove `dst` cannot alias `src` should
+// call copy_val_no_check directly instead of going through here.
 fn copy_val(cx: block, action: copy_action, dst: ValueRef,
             src: ValueRef, t: ty::t) -> block {
     if action == DROP_EXISTING &&
@@ -1487,11 +1921,15 @@ fn copy_val_no_check(bcx: block, action: copy_action, dst: ValueRef,
 
 // This works like copy_val, except that it deinitializes the source.
 // Since it needs to zero out the source, src also needs to be an lval.
-// FIXME: We always zero out the source. Ideally we would detect the
-// case where a variable is always deinitialized by block exit and thus
-// doesn't need to be dropped.
+// `last_use` should be true when the caller has confirmed (via
+// ccx.last_uses) that src's slot is never read again -- in that case we can
+// just revoke the slot's drop-glue cleanup instead of zeroing it, since the
+// zero was only ever there to make that cleanup a safe no-op. Otherwise
+// (the slot could still be observed or dropped again, e.g. it names a local
+// that's live past this point) we fall back to the old always-zero
+// behavior.
 fn move_val(cx: block, action: copy_action, dst: ValueRef,
-            src: lval_result, t: ty::t) -> block {
+            src: lval_result, t: ty::t, last_use: bool) -> block {
     let src_val = src.val;
     let tcx = cx.tcx(), cx = cx;
     if ty::type_is_scalar(t) {
@@ -1504,14 +1942,26 @@ fn move_val(cx: block, action: copy_action, dst: ValueRef,
         if src.kind == owned { src_val = Load(cx, src_val); }
         if action == DROP_EXISTING { cx = drop_ty(cx, dst, t); }
         Store(cx, src_val, dst);
-        if src.kind == owned { ret zero_alloca(cx, src.val, t); }
+        if src.kind == owned {
+            if last_use && ty::type_needs_drop(tcx, t) {
+                revoke_clean(cx, src.val);
+                ret cx;
+            }
+            ret zero_alloca(cx, src.val, t);
+        }
         // If we're here, it must be a temporary.
         revoke_clean(cx, src_val);
         ret cx;
     } else if type_is_structural_or_param(t) {
         if action == DROP_EXISTING { cx = drop_ty(cx, dst, t); }
         cx = memmove_ty(cx, dst, src_val, t);
-        if src.kind == owned { ret zero_alloca(cx, src_val, t); }
+        if src.kind == owned {
+            if last_use && ty::type_needs_drop(tcx, t) {
+                revoke_clean(cx, src_val);
+                ret cx;
+            }
+            ret zero_alloca(cx, src_val, t);
+        }
         // If we're here, it must be a temporary.
         revoke_clean(cx, src_val);
         ret cx;
@@ -1532,7 +1982,7 @@ fn store_temp_expr(cx: block, action: copy_action, dst: ValueRef,
                 };
         ret copy_val(cx, action, dst, v, t);
     }
-    ret move_val(cx, action, dst, src, t);
+    ret move_val(cx, action, dst, src, t, last_use);
 }
 
 fn trans_crate_lit(cx: crate_ctxt, lit: ast::lit) -> ValueRef {
@@ -1567,7 +2017,7 @@ fn trans_unary(bcx: block, op: ast::unop, e: @ast::expr,
         let fty = node_id_type(bcx, callee_id);
         ret trans_call_inner(bcx, fty, {|bcx|
             impl::trans_method_callee(bcx, callee_id, e, origin)
-        }, [], un_expr.id, dest);
+        }, [], un_expr.id, dest, none);
       }
       _ {}
     }
@@ -1612,6 +2062,102 @@ fn trans_unary(bcx: block, op: ast::unop, e: @ast::expr,
     }
 }
 
+// If `t` is a record or tuple with no more than inline_cmp_max_fields
+// fields, all of them scalar, and it isn't dynamically sized, returns the
+// field types in order; otherwise none. Any non-scalar field (including a
+// nested record/tuple) or a dynamically sized field forces a none, so
+// trans_compare falls back to the cmp-glue path for those.
+fn fields_for_inline_cmp(tcx: ty::ctxt, t: ty::t) -> option<[ty::t]> {
+    const inline_cmp_max_fields: uint = 4u;
+    if ty::type_has_dynamic_size(tcx, t) { ret none; }
+    let field_tys = alt ty::get(t).struct {
+      ty::ty_rec(fields) {
+        vec::map(fields) {|fld| fld.mt.ty }
+      }
+      ty::ty_tup(args) { args }
+      _ { ret none; }
+    };
+    if field_tys.len() == 0u || field_tys.len() > inline_cmp_max_fields {
+        ret none;
+    }
+    for fty in field_tys {
+        if !ty::type_is_scalar(fty) { ret none; }
+    }
+    ret some(field_tys);
+}
+
+// Lexicographically compares the fields of two records or tuples known (via
+// fields_for_inline_cmp) to be all scalar, short-circuiting as soon as a
+// field decides the answer -- the same semantics call_cmp_glue's runtime
+// walk implements, just inlined so the common small-record/tuple case
+// avoids the upcall.
+fn trans_compare_inline(cx: block, op: ast::binop, lhs: ValueRef,
+                        rhs: ValueRef, t: ty::t,
+                        field_tys: [ty::t]) -> result {
+    let bcx = cx;
+    let n = field_tys.len();
+
+    // GEP and load every field up front; each load is a plain scalar load
+    // (fields_for_inline_cmp already required every field to be scalar), so
+    // there's no cleanup or control flow to worry about threading through.
+    let lvals = [], rvals = [];
+    let i = 0u;
+    while i < n {
+        let lfld = GEP_tup_like(bcx, t, lhs, [0, i as int]);
+        bcx = lfld.bcx;
+        let rfld = GEP_tup_like(bcx, t, rhs, [0, i as int]);
+        bcx = rfld.bcx;
+        lvals += [load_if_immediate(bcx, lfld.val, field_tys[i])];
+        rvals += [load_if_immediate(bcx, rfld.val, field_tys[i])];
+        i += 1u;
+    }
+
+    alt op {
+      ast::eq | ast::ne {
+        let eq_all = C_bool(true);
+        i = 0u;
+        while i < n {
+            let fld_eq =
+                compare_scalar_types(bcx, lvals[i], rvals[i], field_tys[i],
+                                     ast::eq);
+            bcx = fld_eq.bcx;
+            eq_all = And(bcx, eq_all, fld_eq.val);
+            i += 1u;
+        }
+        ret rslt(bcx, if op == ast::eq { eq_all } else { Not(bcx, eq_all) });
+      }
+      ast::lt | ast::le | ast::gt | ast::ge {
+        let strict_op = alt op {
+          ast::lt | ast::le { ast::lt }
+          _ { ast::gt }
+        };
+        // Lexicographic order says: the last field decides using the real
+        // (possibly non-strict) op, and each earlier field decides outright
+        // with the strict op unless it's equal to its counterpart, in which
+        // case whatever the fields after it already worked out (folded into
+        // `acc`) carries through instead.
+        let last = n - 1u;
+        let last_r = compare_scalar_types(bcx, lvals[last], rvals[last],
+                                          field_tys[last], op);
+        bcx = last_r.bcx;
+        let acc = last_r.val;
+        let idx = last;
+        while idx > 0u {
+            idx -= 1u;
+            let eq_r = compare_scalar_types(bcx, lvals[idx], rvals[idx],
+                                            field_tys[idx], ast::eq);
+            bcx = eq_r.bcx;
+            let strict_r = compare_scalar_types(bcx, lvals[idx], rvals[idx],
+                                                field_tys[idx], strict_op);
+            bcx = strict_r.bcx;
+            acc = Select(bcx, eq_r.val, acc, strict_r.val);
+        }
+        ret rslt(bcx, acc);
+      }
+      _ { cx.tcx().sess.bug("trans_compare_inline got non-comparison-op"); }
+    }
+}
+
 fn trans_compare(cx: block, op: ast::binop, lhs: ValueRef,
                  _lhs_t: ty::t, rhs: ValueRef, rhs_t: ty::t) -> result {
     if ty::type_is_scalar(rhs_t) {
@@ -1619,6 +2165,13 @@ fn trans_compare(cx: block, op: ast::binop, lhs: ValueRef,
       ret rslt(rs.bcx, rs.val);
     }
 
+    alt fields_for_inline_cmp(cx.tcx(), rhs_t) {
+      some(field_tys) {
+        ret trans_compare_inline(cx, op, lhs, rhs, rhs_t, field_tys);
+      }
+      none { }
+    }
+
     // Determine the operation we need.
     let llop;
     alt op {
@@ -1641,6 +2194,31 @@ fn trans_compare(cx: block, op: ast::binop, lhs: ValueRef,
     }
 }
 
+// If `rhs` is a constant power-of-two integer, returns the corresponding
+// bitmask (val - 1), for lowering unsigned `x % val` to `x & mask`.
+fn const_pow2_mask(rhs: ValueRef) -> option<ValueRef> unsafe {
+    if llvm::LLVMIsConstant(rhs) == False { ret none; }
+    let v = llvm::LLVMConstIntGetZExtValue(rhs);
+    if v != 0u64 && (v & (v - 1u64)) == 0u64 {
+        ret some(llvm::LLVMConstInt(llvm::LLVMTypeOf(rhs), v - 1u64, False));
+    }
+    ret none;
+}
+
+// If `rhs` is a constant power-of-two integer, returns the shift amount
+// for lowering unsigned `x / val` to `x >> shift`.
+fn const_pow2_shift(rhs: ValueRef) -> option<ValueRef> unsafe {
+    if llvm::LLVMIsConstant(rhs) == False { ret none; }
+    let v = llvm::LLVMConstIntGetZExtValue(rhs);
+    if v != 0u64 && (v & (v - 1u64)) == 0u64 {
+        let shift = 0u64;
+        let n = v;
+        while n > 1u64 { n >>= 1u64; shift += 1u64; }
+        ret some(llvm::LLVMConstInt(llvm::LLVMTypeOf(rhs), shift, False));
+    }
+    ret none;
+}
+
 // Important to get types for both lhs and rhs, because one might be _|_
 // and the other not.
 fn trans_eager_binop(cx: block, op: ast::binop, lhs: ValueRef,
@@ -1671,13 +2249,23 @@ fn trans_eager_binop(cx: block, op: ast::binop, lhs: ValueRef,
         if is_float { FDiv(cx, lhs, rhs) }
         else if ty::type_is_signed(intype) {
             SDiv(cx, lhs, rhs)
-        } else { UDiv(cx, lhs, rhs) }
+        } else {
+            alt const_pow2_shift(rhs) {
+              some(shift) { LShr(cx, lhs, shift) }
+              none { UDiv(cx, lhs, rhs) }
+            }
+        }
       }
       ast::rem {
         if is_float { FRem(cx, lhs, rhs) }
         else if ty::type_is_signed(intype) {
             SRem(cx, lhs, rhs)
-        } else { URem(cx, lhs, rhs) }
+        } else {
+            alt const_pow2_mask(rhs) {
+              some(mask) { And(cx, lhs, mask) }
+              none { URem(cx, lhs, rhs) }
+            }
+        }
       }
       ast::bitor { Or(cx, lhs, rhs) }
       ast::bitand { And(cx, lhs, rhs) }
@@ -1708,7 +2296,7 @@ fn trans_assign_op(bcx: block, ex: @ast::expr, op: ast::binop,
         ret trans_call_inner(bcx, fty, {|bcx|
             // FIXME provide the already-computed address, not the expr
             impl::trans_method_callee(bcx, callee_id, dst, origin)
-        }, [src], ex.id, save_in(lhs_res.val));
+        }, [src], ex.id, save_in(lhs_res.val), none);
       }
       _ {}
     }
@@ -1818,7 +2406,7 @@ fn trans_binary(bcx: block, op: ast::binop, lhs: @ast::expr,
         let fty = node_id_type(bcx, callee_id);
         ret trans_call_inner(bcx, fty, {|bcx|
             impl::trans_method_callee(bcx, callee_id, lhs, origin)
-        }, [rhs], ex.id, dest);
+        }, [rhs], ex.id, dest, none);
       }
       _ {}
     }
@@ -1842,11 +2430,63 @@ fn trans_binary(bcx: block, op: ast::binop, lhs: @ast::expr,
     }
 }
 
+// A "trivial" block is one with no statements and a tail expression that
+// is guaranteed side-effect-free and immediate: a literal or a read of a
+// local variable. trans_if uses this to recognize `if c {a} else {b}`
+// arms that can become a single Select rather than two basic blocks
+// joined by a phi.
+fn trivial_tail_expr(blk: ast::blk) -> option<@ast::expr> {
+    if vec::is_not_empty(blk.node.stmts) { ret none; }
+    alt blk.node.expr {
+      some(e) {
+        alt e.node {
+          ast::expr_lit(_) | ast::expr_path(_) { some(e) }
+          _ { none }
+        }
+      }
+      none { none }
+    }
+}
+
 fn trans_if(cx: block, cond: @ast::expr, thn: ast::blk,
             els: option<@ast::expr>, dest: dest)
     -> block {
     let {bcx, val: cond_val} = trans_temp_expr(cx, cond);
 
+    // Fast path: both arms are trivial immediates, so skip the
+    // then/else basic blocks and phi and emit a Select directly.
+    alt dest {
+      by_val(cell) {
+        let els_blk = alt els {
+          some(e) {
+            alt e.node {
+              ast::expr_block(b) { some(b) }
+              _ { none }
+            }
+          }
+          none { none }
+        };
+        alt (trivial_tail_expr(thn), els_blk) {
+          (some(then_e), some(els_blk)) {
+            alt trivial_tail_expr(els_blk) {
+              some(else_e) {
+                if ty::type_is_immediate(expr_ty(bcx, then_e)) {
+                    let {bcx, val: then_val} = trans_temp_expr(bcx, then_e);
+                    let {bcx, val: else_val} = trans_temp_expr(bcx, else_e);
+                    let v = Select(bcx, cond_val, then_val, else_val);
+                    *cell = v;
+                    ret bcx;
+                }
+              }
+              none {}
+            }
+          }
+          _ {}
+        }
+      }
+      _ {}
+    }
+
     let then_dest = dup_for_join(dest);
     let else_dest = dup_for_join(dest);
     let then_cx = scope_block(bcx, "then");
@@ -1898,6 +2538,10 @@ fn trans_for(cx: block, local: @ast::local, seq: @ast::expr,
         ret next_cx;
     }
     let ccx = cx.ccx();
+    alt ccx.method_map.find(seq.id) {
+      some(origin) { ret trans_for_iter(cx, local, seq, body, origin, inner); }
+      none {}
+    }
     let next_cx = sub_block(cx, "next");
     let seq_ty = expr_ty(cx, seq);
     let {bcx: bcx, val: seq} = trans_temp_expr(cx, seq);
@@ -1912,6 +2556,117 @@ fn trans_for(cx: block, local: @ast::local, seq: @ast::expr,
     ret next_cx;
 }
 
+// Lowers `for pat in seq { body }` when `seq` doesn't have the structural
+// vector/string representation `tvec::iter_vec_raw` expects, but its type
+// was resolved (during typeck, into `ccx.method_map`) to have a `next`
+// method following the iteration protocol: `next() -> option<T>`. Each
+// iteration calls `next()` and switches on the resulting option's
+// discriminant exactly the way `iter_structural_ty`'s `ty::ty_enum` arm
+// does, feeding the payload pointer of the `some` variant to the same
+// `inner` helper `trans_for` uses for the vector/string path.
+fn trans_for_iter(cx: block, local: @ast::local, seq: @ast::expr,
+                  body: ast::blk, origin: typeck::method_origin,
+                  inner: fn(block, @ast::local, ValueRef, ty::t,
+                            ast::blk, block) -> block) -> block {
+    let ccx = cx.ccx();
+    let elt_ty = node_id_type(cx, local.node.id);
+    let loop_cx = sub_block(cx, "iter loop");
+    let next_cx = sub_block(cx, "next");
+    Br(cx, loop_cx.llbb);
+
+    let fn_ty = node_id_type(loop_cx, seq.id);
+    let opt_ty = ty::ty_fn_ret(fn_ty);
+    let {bcx, val: optval} = alloc_ty(loop_cx, opt_ty);
+    let callee = impl::trans_method_callee(bcx, seq.id, seq, origin);
+    let bcx = trans_iter_next_call(callee, fn_ty, save_in(optval));
+
+    let (tid, tps) = alt check ty::get(opt_ty).struct {
+      ty::ty_enum(tid, tps) { (tid, tps) }
+    };
+    let variants = ty::enum_variants(ccx.tcx, tid);
+    let none_variant = variants[0], some_variant = variants[1];
+
+    let llenumty = T_opaque_enum_ptr(ccx);
+    let av_enum = PointerCast(bcx, optval, llenumty);
+    let lldiscrim = Load(bcx, GEPi(bcx, av_enum, [0, 0]));
+    let llunion_a_ptr = GEPi(bcx, av_enum, [0, 1]);
+
+    let unr_cx = sub_block(bcx, "iter-unr");
+    Unreachable(unr_cx);
+    let llswitch = Switch(bcx, lldiscrim, unr_cx.llbb, 2u);
+
+    let none_cx = sub_block(bcx, "iter-none");
+    AddCase(llswitch, C_int(ccx, none_variant.disr_val), none_cx.llbb);
+    Br(none_cx, next_cx.llbb);
+
+    let some_cx = sub_block(bcx, "iter-some");
+    AddCase(llswitch, C_int(ccx, some_variant.disr_val), some_cx.llbb);
+    check (valid_variant_index(0u, some_cx, tid, some_variant.id));
+    let {bcx: some_cx, val: curr} =
+        GEP_enum(some_cx, llunion_a_ptr, tid, some_variant.id, tps, 0u);
+    let body_end = inner(some_cx, local, curr, elt_ty, body, next_cx);
+    Br(body_end, loop_cx.llbb);
+
+    ret next_cx;
+}
+
+// A minimal counterpart to trans_call_inner for calling a resolved method
+// with no explicit arguments, such as `next()` in trans_for_iter above.
+// Unlike trans_call_inner, there's no call expr node id to pull the
+// return type from (the call is synthesized, not written by the user),
+// so the return type is derived directly from the callee's function type
+// instead.
+fn trans_iter_next_call(callee: lval_maybe_callee, fn_ty: ty::t,
+                        dest: dest) -> block {
+    with_scope(callee.bcx, "iter next call") {|cx|
+        let bcx = cx, ccx = cx.ccx();
+        let faddr = callee.val;
+        let llenv, dict_param = none;
+        alt callee.env {
+          null_env {
+            llenv = llvm::LLVMGetUndef(T_opaque_box_ptr(ccx));
+          }
+          self_env(e, _) {
+            llenv = PointerCast(bcx, e, T_opaque_box_ptr(ccx));
+          }
+          dict_env(dict, e) {
+            llenv = PointerCast(bcx, e, T_opaque_box_ptr(ccx));
+            dict_param = some(dict);
+          }
+          is_closure {
+            if callee.kind == owned {
+                faddr = load_if_immediate(bcx, faddr, fn_ty);
+            }
+            let pair = faddr;
+            faddr = GEPi(bcx, pair, [0, abi::fn_field_code]);
+            faddr = Load(bcx, faddr);
+            let llclosure = GEPi(bcx, pair, [0, abi::fn_field_box]);
+            llenv = Load(bcx, llclosure);
+          }
+        }
+
+        let ret_ty = ty::ty_fn_ret(fn_ty);
+        let args_res = trans_args(bcx, llenv, callee.generic, [], fn_ty, dest);
+        bcx = args_res.bcx;
+        let llargs = args_res.args;
+        option::may(dict_param) {|dict| llargs = [dict] + llargs}
+        let llretslot = args_res.retslot;
+
+        bcx = invoke_cc(bcx, faddr, llargs, callee.cc);
+        alt dest {
+          ignore {
+            if llvm::LLVMIsUndef(llretslot) != lib::llvm::True {
+                bcx = drop_ty(bcx, llretslot, ret_ty);
+            }
+          }
+          save_in(_) { } // Already saved by callee
+          by_val(cell) { *cell = Load(bcx, llretslot); }
+        }
+        if ty::type_is_bot(ret_ty) { Unreachable(bcx); }
+        bcx
+    }
+}
+
 fn trans_while(cx: block, cond: @ast::expr, body: ast::blk)
     -> block {
     let next_cx = sub_block(cx, "while next");
@@ -1972,7 +2727,8 @@ type lval_maybe_callee = {bcx: block,
                           val: ValueRef,
                           kind: lval_kind,
                           env: callee_env,
-                          generic: generic_callee};
+                          generic: generic_callee,
+                          cc: lib::llvm::CallConv};
 
 fn null_env_ptr(bcx: block) -> ValueRef {
     C_null(T_opaque_box_ptr(bcx.ccx()))
@@ -1992,7 +2748,7 @@ fn lval_temp(bcx: block, val: ValueRef) -> lval_result {
 fn lval_no_env(bcx: block, val: ValueRef, kind: lval_kind)
     -> lval_maybe_callee {
     ret {bcx: bcx, val: val, kind: kind, env: is_closure,
-         generic: generic_none};
+         generic: generic_none, cc: lib::llvm::CCallConv};
 }
 
 fn trans_external_path(cx: block, did: ast::def_id,
@@ -2003,6 +2759,16 @@ fn trans_external_path(cx: block, did: ast::def_id,
                          type_of_ty_param_bounds_and_ty(ccx, tpt));
 }
 
+// A monomorphized body never needs a separate pass to specialize away
+// copy_val/take_ty/drop_ty's glue dispatch for a `Copy`-bound type
+// parameter substituted with a scalar type: `psubsts` below makes
+// node_id_type/expr_ty (common.rs) substitute the concrete type in for
+// every occurrence of the type parameter throughout the body, so
+// copy_val_no_check/take_ty/drop_ty already see the real scalar `ty::t`
+// (not a `ty_param`) and take their existing `ty::type_is_scalar`/
+// `ty::type_needs_drop` fast paths -- a plain `Store`, no tydesc glue
+// call, and (since copy_val's self-copy guard only fires for
+// `type_is_structural_or_param`) no self-copy check either.
 fn monomorphic_fn(ccx: crate_ctxt, fn_id: ast::def_id, substs: [ty::t],
                   dicts: option<typeck::dict_res>)
     -> option<{llfn: ValueRef, fty: ty::t}> {
@@ -2099,9 +2865,13 @@ fn lval_static_fn(bcx: block, fn_id: ast::def_id, id: ast::node_id,
         };
         alt mono {
           some({llfn, fty}) {
+            // Monomorphized fns are always declared with the default
+            // Rust calling convention (see monomorphic_fn); a
+            // #[abi]-selected convention on a generic fn isn't
+            // meaningful the way it is for a concrete one.
             ret {bcx: bcx, val: llfn,
                  kind: owned, env: null_env,
-                 generic: generic_mono(fty)};
+                 generic: generic_mono(fty), cc: lib::llvm::CCallConv};
           }
           none {}
         }
@@ -2143,9 +2913,37 @@ fn lval_static_fn(bcx: block, fn_id: ast::def_id, id: ast::node_id,
                             param_bounds: tpt.bounds,
                             origins: ccx.dict_map.find(id)});
     }
-    ret {bcx: bcx, val: val, kind: owned, env: null_env, generic: gen};
-}
-
+    // FIXME: Need to support external crust functions
+    //
+    // An external-crate fn has no entry in item_ccs (that table is only
+    // populated for fns register_fn_fuller declares in this crate), and
+    // crate metadata doesn't currently encode a callee's #[abi]-selected
+    // convention either, so cross-crate calls always fall back to the
+    // default Rust convention here.
+    let cc = if fn_id.crate == ast::local_crate {
+        alt ccx.item_ccs.find(fn_id.node) {
+          some(cc) { cc }
+          none { lib::llvm::CCallConv }
+        }
+    } else {
+        lib::llvm::CCallConv
+    };
+    ret {bcx: bcx, val: val, kind: owned, env: null_env, generic: gen,
+         cc: cc};
+}
+
+// The `discriminant_value` rust-intrinsic (core::unsafe) has no
+// repr-typed variant: it reads the tag as a full int word, just as every
+// enum's discriminant, wherever it's read here or in trans_enum_variant
+// below, is always loaded/stored as a plain ty::mk_int word (see e.g.
+// trans_var's nullary-variant case and trans_enum_variant's
+// `Store(bcx, C_int(ccx, disr), lldiscrimptr)`), and attr::find_repr_attr
+// has nothing to hang a narrower-width
+// discriminant load on: enum layout here is shape/tydesc-driven with the
+// tag always occupying a full int-sized slot at element 0, not the
+// C-style fixed-width leading tag a `#[repr(u8)]` enum needs. Adding a
+// repr-typed discriminant read requires that repr-C enum layout to exist
+// first, which it doesn't yet in this tree.
 fn lookup_discriminant(ccx: crate_ctxt, vid: ast::def_id) -> ValueRef {
     alt ccx.discrims.find(vid) {
       none {
@@ -2232,6 +3030,16 @@ fn trans_var(cx: block, def: ast::def, id: ast::node_id)
       ast::def_const(did) {
         if did.crate == ast::local_crate {
             assert (ccx.consts.contains_key(did.node));
+            // No special casing needed here for a #[thread_local] const
+            // (see collect_item's LLVMSetThreadLocal call): this hands
+            // back the GlobalVariable's own ValueRef as the lvalue's
+            // address, same as for any other global, and every caller
+            // reaches it through an ordinary Load/Store (see
+            // load_if_immediate below and the assignment path in
+            // trans_lval) rather than folding it to a constant. LLVM
+            // lowers the thread-local addressing itself based on the
+            // thread_local flag on the GlobalVariable; nothing in trans
+            // needs to know the const is thread-local past this point.
             ret lval_no_env(cx, ccx.consts.get(did.node), owned);
         } else {
             let tp = node_id_type(cx, id);
@@ -2450,7 +3258,34 @@ fn trans_cast(cx: block, e: @ast::expr, id: ast::node_id,
     let newval =
         alt {in: k_in, out: k_out} {
           {in: integral, out: integral} {
-            int_cast(e_res.bcx, ll_t_out, ll_t_in, e_res.val, s_in)
+            let is_char_out = alt ty::get(t_out).struct {
+              ty::ty_int(ast::ty_char) { true }
+              _ { false }
+            };
+            if is_char_out {
+                // An arbitrary int cast to char can name a value that
+                // isn't a valid Unicode scalar value (out of range, or
+                // in the surrogate range); guard against truncating
+                // into a bogus char rather than letting it through.
+                let cx = e_res.bcx;
+                let max = C_integral(ll_t_in, 0x10FFFFu64, False);
+                let surr_lo = C_integral(ll_t_in, 0xD800u64, False);
+                let surr_hi = C_integral(ll_t_in, 0xDFFFu64, False);
+                let too_big = ICmp(cx, lib::llvm::IntUGT, e_res.val, max);
+                let in_surrogates =
+                    And(cx, ICmp(cx, lib::llvm::IntUGE, e_res.val, surr_lo),
+                        ICmp(cx, lib::llvm::IntULE, e_res.val, surr_hi));
+                let out_of_range = Or(cx, too_big, in_surrogates);
+                cx = with_cond(cx, out_of_range) {|bcx|
+                    trans_fail(bcx, some(e.span),
+                               "cast to char out of range")
+                };
+                let v = int_cast(cx, ll_t_out, ll_t_in, e_res.val, s_in);
+                e_res = {bcx: cx, val: e_res.val};
+                v
+            } else {
+                int_cast(e_res.bcx, ll_t_out, ll_t_in, e_res.val, s_in)
+            }
           }
           {in: float, out: float} {
             float_cast(e_res.bcx, ll_t_out, ll_t_in, e_res.val)
@@ -2461,9 +3296,48 @@ fn trans_cast(cx: block, e: @ast::expr, id: ast::node_id,
             } else { UIToFP(e_res.bcx, e_res.val, ll_t_out) }
           }
           {in: float, out: integral} {
-            if ty::type_is_signed(t_out) {
-                FPToSI(e_res.bcx, e_res.val, ll_t_out)
-            } else { FPToUI(e_res.bcx, e_res.val, ll_t_out) }
+            // Plain FPToSI/FPToUI are undefined in LLVM when the float is
+            // out of the destination's range or NaN, so clamp instead of
+            // trusting the raw conversion.
+            let cx = e_res.bcx;
+            let signed = ty::type_is_signed(t_out);
+            let width = llvm::LLVMGetIntTypeWidth(ll_t_out) as uint;
+            // The high threshold below is compared with OGE against a
+            // power of two (2^(width-1) signed, 2^width unsigned) rather
+            // than OGT against the destination's actual max value
+            // (2^(width-1)-1 / 2^width-1): a power of two is exactly
+            // representable in any binary float regardless of ll_t_in's
+            // precision, but the true max value isn't once width exceeds
+            // the source float's mantissa (e.g. an i64/u64 max cast from
+            // f32 or f64). Formatting that max and parsing it back as
+            // ll_t_in would silently round it up to the next power of
+            // two, moving the boundary and letting an exact power-of-two
+            // float value (a perfectly ordinary finite input) fall
+            // through to a raw FPToSI/FPToUI on an out-of-range operand
+            // instead of being caught by the clamp.
+            let (min_str, hi_str, min_val, max_val) = if signed {
+                let half = 1u64 << (width - 1u);
+                ("-" + u64::str(half), u64::str(half),
+                 C_integral(ll_t_out, half, True),
+                 C_integral(ll_t_out, half - 1u64, True))
+            } else {
+                let max_u = if width >= 64u { 0xFFFFFFFFFFFFFFFFu64 }
+                            else { (1u64 << width) - 1u64 };
+                let hi_str = if width >= 64u { "18446744073709551616" }
+                             else { u64::str(1u64 << width) };
+                ("0", hi_str, C_integral(ll_t_out, 0u64, False),
+                 C_integral(ll_t_out, max_u, False))
+            };
+            let fmin = C_floating(min_str, ll_t_in);
+            let fhi = C_floating(hi_str, ll_t_in);
+            let is_nan = FCmp(cx, lib::llvm::RealUNO, e_res.val, e_res.val);
+            let too_low = FCmp(cx, lib::llvm::RealOLT, e_res.val, fmin);
+            let too_high = FCmp(cx, lib::llvm::RealOGE, e_res.val, fhi);
+            let raw = if signed { FPToSI(cx, e_res.val, ll_t_out) }
+                      else { FPToUI(cx, e_res.val, ll_t_out) };
+            let clamped = Select(cx, too_low, min_val, raw);
+            clamped = Select(cx, too_high, max_val, clamped);
+            Select(cx, is_nan, C_integral(ll_t_out, 0u64, False), clamped)
           }
           {in: integral, out: pointer} {
             IntToPtr(e_res.bcx, e_res.val, ll_t_out)
@@ -2487,6 +3361,37 @@ fn trans_cast(cx: block, e: @ast::expr, id: ast::node_id,
               _ { ccx.sess.bug("Translating unsupported cast.") }
             }
           }
+          {in: enum_, out: enum_} {
+            // Only defined between two C-like (all-nullary-variant)
+            // enums, so this is purely a discriminant copy -- useful for
+            // interop between enums that otherwise share a layout.
+            fn all_nullary(ccx: crate_ctxt, did: ast::def_id) -> bool {
+                vec::all(*ty::enum_variants(ccx.tcx, did),
+                        {|v| v.args.len() == 0u})
+            }
+            let (in_did, out_did) = alt {a: ty::get(t_in).struct,
+                                         b: ty::get(t_out).struct} {
+              {a: ty::ty_enum(a, _), b: ty::ty_enum(b, _)} { (a, b) }
+              _ { ccx.sess.bug("enum-to-enum cast on a non-enum type") }
+            };
+            if !all_nullary(ccx, in_did) || !all_nullary(ccx, out_did) {
+                ccx.sess.span_fatal(
+                    e.span,
+                    "cannot cast between enums that have variants " +
+                    "carrying data");
+            }
+            let cx = e_res.bcx;
+            let llenumty = T_opaque_enum_ptr(ccx);
+            let av_enum = PointerCast(cx, e_res.val, llenumty);
+            let lldiscrim = Load(cx, GEPi(cx, av_enum, [0, 0]));
+            let dest = alloc_ty(cx, t_out);
+            cx = dest.bcx;
+            let dest_enum = PointerCast(cx, dest.val, llenumty);
+            Store(cx, lldiscrim, GEPi(cx, dest_enum, [0, 0]));
+            let v = load_if_immediate(cx, dest.val, t_out);
+            e_res = {bcx: cx, val: e_res.val};
+            v
+          }
           _ { ccx.sess.bug("Translating unsupported cast.") }
         };
     ret store_in_dest(e_res.bcx, newval, dest);
@@ -2532,9 +3437,19 @@ fn trans_arg_expr(cx: block, arg: ty::arg, lldestty: TypeRef,
         bcx = cx;
         if lv.kind == temporary { revoke_clean(bcx, val); }
         if lv.kind == owned || !ty::type_is_immediate(e_ty) {
-            bcx = memmove_ty(bcx, alloc, val, e_ty);
+            // alloc was just allocated above, so it can't overlap val.
+            bcx = memcpy_ty(bcx, alloc, val, e_ty);
             if move_out && ty::type_needs_drop(ccx.tcx, e_ty) {
-                bcx = zero_alloca(bcx, val, e_ty);
+                // An explicit `move` doesn't by itself guarantee this is
+                // e's last use (the source could still be a live local
+                // that's read again later), but last_uses does -- so only
+                // revoke the source's cleanup, instead of zeroing it, when
+                // last_uses has confirmed it.
+                if lv.kind == owned && ccx.last_uses.contains_key(e.id) {
+                    revoke_clean(bcx, val);
+                } else {
+                    bcx = zero_alloca(bcx, val, e_ty);
+                }
             }
         } else { Store(bcx, val, alloc); }
         val = alloc;
@@ -2657,16 +3572,122 @@ fn trans_args(cx: block, llenv: ValueRef,
          retslot: llretslot};
 }
 
+// Names the `unpredictable` rust-intrinsic by its final path segment; the
+// intrinsic itself is declared like any other via the generic
+// native_abi_rust_intrinsic path in collect_native_item, so this is the
+// only special-casing it needs.
+fn is_unpredictable_callee(f: @ast::expr) -> bool {
+    alt f.node {
+      ast::expr_path(pth) {
+        pth.node.idents.len() > 0u &&
+            pth.node.idents[pth.node.idents.len() - 1u] == "unpredictable"
+      }
+      _ { false }
+    }
+}
+
+// If `f` is a path naming a local (this-crate) function directly (as
+// opposed to through a closure, dict, or method-call dispatch), returns
+// its def_id, generic or not (inline_decision_for_call skips generic ones) -- the only case where a call site has
+// both a specific callee body to size up and a specific instruction to
+// hang a per-call-site inlining hint off of. See inline_decision_for_call.
+fn direct_local_callee(cx: block, f: @ast::expr) -> option<ast::def_id> {
+    alt f.node {
+      ast::expr_path(_) {
+        alt cx.tcx().def_map.find(f.id) {
+          some(ast::def_fn(did, _)) if did.crate == ast::local_crate {
+            some(did)
+          }
+          _ { none }
+        }
+      }
+      _ { none }
+    }
+}
+
+// Counts the expression nodes in a function body, as a cheap stand-in for
+// how much code inlining it would duplicate at a call site. Doesn't try
+// to weigh different expression kinds (a call is far more expensive than
+// a literal, but both count as 1 here) -- see inline_decision_for_call
+// for why that coarseness is acceptable for a hint LLVM's own inliner
+// still has final say over.
+fn callee_ast_size(body: ast::blk) -> uint {
+    fn count_expr(n: @mutable uint, _e: @ast::expr) { *n += 1u; }
+    let n = @mutable 0u;
+    let v = visit::mk_simple_visitor(
+        @{visit_expr: bind count_expr(n, _)
+          with *visit::default_simple_visitor()});
+    visit::visit_block(body, (), v);
+    ret *n;
+}
+
+// At or below inline_hint_max_size, a direct call is hinted AlwaysInline
+// at just this call site; at or above no_inline_hint_min_size, NoInline.
+// In between, no hint is given and
+// LLVM's own cost-based inliner decides as it would have anyway.
+const inline_hint_max_size: uint = 10u;
+const no_inline_hint_min_size: uint = 200u;
+
+// A per-call-site inlining hint: for a direct call to a local,
+// non-generic function (the only case where both a specific callee body
+// and a specific call instruction are available to reason about), a
+// small enough callee is hinted AlwaysInline at just this call site --
+// and, symmetrically, a large one NoInline -- via the Call/Invoke
+// instruction's own attribute rather than the callee definition's, so a
+// small function called once from a hot loop and once from cold
+// error-handling code can be inlined at the first site without also
+// being force-inlined (and duplicated) at the second.
+//
+// This is only a hint: AlwaysInlineAttribute/NoInlineAttribute on a call
+// site is honored by LLVM's own inliner pass, not a from-scratch inliner
+// of our own -- actually duplicating the callee's body into the caller
+// here in trans, as a true partial inliner would, is out of scope for
+// this hook.
+//
+// #[inline(always)]/#[inline(never)] (attr::il_always/il_never, applied
+// to the callee's definition in trans_item) already settle the question
+// for every call to a given function; this cost model only gets a say
+// when the callee carries no #[inline] attribute at all (a plain
+// #[inline] hint also leaves its call sites unhinted).
+fn inline_decision_for_call(ccx: crate_ctxt, direct_callee: option<ast::def_id>)
+    -> option<bool> {
+    alt direct_callee {
+      none { none }
+      some(did) {
+        alt ccx.tcx.items.find(did.node) {
+          some(ast_map::node_item(@{node: ast::item_fn(_, tps, body), attrs,
+                                    _}, _))
+              if tps.len() == 0u {
+            alt attr::find_inline_attr(attrs) {
+              attr::il_none {
+                let n = callee_ast_size(body);
+                if n <= inline_hint_max_size { some(true) }
+                else if n >= no_inline_hint_min_size { some(false) }
+                else { none }
+              }
+              attr::il_hint | attr::il_always | attr::il_never { none }
+            }
+          }
+          _ { none }
+        }
+      }
+    }
+}
+
 fn trans_call(in_cx: block, f: @ast::expr,
               args: [@ast::expr], id: ast::node_id, dest: dest)
     -> block {
-    trans_call_inner(in_cx, expr_ty(in_cx, f),
-                     {|cx| trans_callee(cx, f)}, args, id, dest)
+    let bcx = trans_call_inner(in_cx, expr_ty(in_cx, f),
+                               {|cx| trans_callee(cx, f)}, args, id, dest,
+                               direct_local_callee(in_cx, f));
+    if is_unpredictable_callee(f) { bcx.ccx().pending_unpredictable = true; }
+    ret bcx;
 }
 
 fn trans_call_inner(in_cx: block, fn_expr_ty: ty::t,
                     get_callee: fn(block) -> lval_maybe_callee,
-                    args: [@ast::expr], id: ast::node_id, dest: dest)
+                    args: [@ast::expr], id: ast::node_id, dest: dest,
+                    direct_callee: option<ast::def_id>)
     -> block {
     with_scope(in_cx, "call") {|cx|
         let f_res = get_callee(cx);
@@ -2710,7 +3731,13 @@ fn trans_call_inner(in_cx: block, fn_expr_ty: ty::t,
         then one or more of the args has
         type _|_. Since that means it diverges, the code
         for the call itself is unreachable. */
-        bcx = invoke_full(bcx, faddr, llargs);
+        let call_res = invoke_cc_val(bcx, faddr, llargs, f_res.cc);
+        bcx = call_res.bcx;
+        alt inline_decision_for_call(ccx, direct_callee) {
+          some(true) { set_call_always_inline(call_res.val); }
+          some(false) { set_call_no_inline(call_res.val); }
+          none { }
+        }
         alt dest {
           ignore {
             if llvm::LLVMIsUndef(llretslot) != lib::llvm::True {
@@ -2729,25 +3756,82 @@ fn trans_call_inner(in_cx: block, fn_expr_ty: ty::t,
 
 fn invoke(bcx: block, llfn: ValueRef,
           llargs: [ValueRef]) -> block {
-    ret invoke_(bcx, llfn, llargs, Invoke);
+    ret invoke_cc(bcx, llfn, llargs, lib::llvm::CCallConv);
 }
 
 fn invoke_full(bcx: block, llfn: ValueRef, llargs: [ValueRef])
     -> block {
-    ret invoke_(bcx, llfn, llargs, Invoke);
-}
-
-fn invoke_(bcx: block, llfn: ValueRef, llargs: [ValueRef],
-           invoker: fn(block, ValueRef, [ValueRef],
-                       BasicBlockRef, BasicBlockRef)) -> block {
-    // FIXME: May be worth turning this into a plain call when there are no
-    // cleanups to run
-    if bcx.unreachable { ret bcx; }
+    ret invoke_cc(bcx, llfn, llargs, lib::llvm::CCallConv);
+}
+
+// Like invoke_full, but for a callee that may have been declared with a
+// non-default calling convention (see attr::find_fn_call_conv /
+// register_fn_fuller). The caller's Call/Invoke instruction has to agree
+// with however the callee was declared, or the two sides disagree on
+// where arguments and the return value live.
+fn invoke_cc(bcx: block, llfn: ValueRef, llargs: [ValueRef],
+            cc: lib::llvm::CallConv) -> block {
+    ret invoke_cc_val(bcx, llfn, llargs, cc).bcx;
+}
+
+// Like invoke_cc, but also hands back the ValueRef of the Call/Invoke
+// instruction it emitted, for a caller (trans_call_inner) that wants to
+// attach a per-call-site attribute to it afterwards. invoke_cc itself
+// stays the plain block-returning helper its other callers (invoke,
+// invoke_full, and the method-callee case above) expect.
+fn invoke_cc_val(bcx: block, llfn: ValueRef, llargs: [ValueRef],
+                 cc: lib::llvm::CallConv) -> {bcx: block, val: ValueRef} {
+    if bcx.unreachable { ret {bcx: bcx, val: llfn}; }
+    // If no enclosing scope has cleanups to run on the unwind path, a
+    // landing pad would never be entered, so emit a plain call instead of
+    // an invoke -- this avoids the cost of the landing pad and lets the
+    // callee be inlined.
+    if !scope_has_cleanups(bcx) {
+        let v = CallWithConv(bcx, llfn, llargs, cc);
+        ret {bcx: bcx, val: v};
+    }
     let normal_bcx = sub_block(bcx, "normal return");
-    invoker(bcx, llfn, llargs, normal_bcx.llbb, get_landing_pad(bcx));
-    ret normal_bcx;
+    let v = InvokeWithConv(bcx, llfn, llargs, normal_bcx.llbb,
+                           get_landing_pad(bcx), cc);
+    ret {bcx: normal_bcx, val: v};
 }
 
+fn scope_has_cleanups(bcx: block) -> bool {
+    let cx = bcx, found = false;
+    while true {
+        alt cx.kind {
+          block_scope(info) {
+            if info.cleanups.len() > 0u { found = true; break; }
+          }
+          _ {}
+        }
+        if cx.parent == parent_none { break; }
+        cx = block_parent(cx);
+    }
+    ret found;
+}
+
+// This and cleanup_and_leave are Itanium-EH-only: llretty is the fixed
+// {i8*, i32} the Itanium personality-function ABI returns, `personality`
+// below is __gxx_personality_v0 (via rust_upcall.cpp) unless --personality
+// names another routine, and unwinding
+// out of the last scope always ends in a plain Resume (see
+// cleanup_and_leave below) rather than a funclet-style cleanupret. Making
+// this scheme selectable per sess.targ_cfg for real Windows x64 SEH
+// funclets -- cleanuppad/cleanupret bracketing every cleanup scope and
+// catchswitch/catchpad/catchret bracketing every catch, each parented by
+// a token the LLVM verifier enforces can't cross block boundaries the way
+// a landing pad's plain BasicBlockRef can -- isn't a local change to
+// these two functions: lib::llvm has no LLVMBuildCleanupPad/CatchPad/
+// CatchSwitch/CatchRet/CleanupRet bindings at all (only LandingPad
+// itself, above), and there's no Microsoft-ABI personality routine
+// anywhere in rust_upcall.cpp to hand one to even if the IR could be
+// built. The one Windows target this compiler actually has (os_win32,
+// i686/x86_64-pc-mingw32 in back/x86.rs and back/x86_64.rs) is a GCC-ABI
+// target, which conventionally still unwinds through a GCC personality
+// routine compatible with this same landingpad/Resume shape -- it's only
+// a *native MSVC* target, which this tree has no triple for, that would
+// actually require SEH funclets instead.
 fn get_landing_pad(bcx: block) -> BasicBlockRef {
     fn in_lpad_scope_cx(bcx: block, f: fn(scope_info)) {
         let bcx = bcx;
@@ -2809,6 +3893,75 @@ fn get_landing_pad(bcx: block) -> BasicBlockRef {
     ret pad_bcx.llbb;
 }
 
+// trans_try wraps `body` in a scope whose landing pad *catches* rather
+// than merely cleans up: unlike get_landing_pad (used for every other
+// invoke, which only runs cleanups and Resumes), the pad installed here
+// carries a catch-all clause, so a fail triggered anywhere inside `body`
+// transfers control to `catch` instead of continuing to unwind past this
+// function. `body` and `catch` are expected to leave their result in a
+// value of the same type; trans_try merges the two paths with a Phi and
+// returns the combined result.
+//
+// This only handles the LLVM/ABI side of catching the unwind. It doesn't
+// attempt any of the task-level bookkeeping a real supervisor would want
+// (e.g. clearing the failed flag, deciding whether locals poisoned by a
+// partially-run `body` are safe to keep using) -- that's runtime/task
+// policy, not something trans can decide on its own.
+//
+// NOT YET USABLE: this only satisfies the "catching landing pad" half of
+// the original request. The other half -- a test that a `fail` inside a
+// protected body runs the catch block and the program continues -- needs
+// a real caller, and there isn't one: no `try`/`catch` keyword exists in
+// the parser, no AST node represents it, and nothing in trans calls this
+// function. Adding that surface syntax is a parser/AST/typeck change, not
+// a trans one, and isn't done here. Until something calls trans_try, this
+// is prerequisite plumbing only, unreachable from any compiled program --
+// not a landed fail-recovery feature.
+fn trans_try(bcx: block, body: fn(block) -> result,
+             catch: fn(block) -> result) -> result {
+    let try_cx = scope_block(bcx, "try");
+    Br(bcx, try_cx.llbb);
+
+    let pad_bcx = sub_block(bcx, "catch");
+    let llretty = T_struct([T_ptr(T_i8()), T_i32()]);
+    let personality = bcx.ccx().upcalls.rust_personality;
+    let llretval = LandingPad(pad_bcx, llretty, personality, 1u);
+    AddClause(pad_bcx, llretval, C_null(T_ptr(T_i8())));
+    alt check try_cx.kind {
+      block_scope(info) { info.landing_pad = some(pad_bcx.llbb); }
+    }
+
+    // As in get_landing_pad, we may have unwound across a stack boundary.
+    Call(pad_bcx, bcx.ccx().upcalls.reset_stack_limit, []);
+
+    // Finish the Itanium unwind protocol that task::fail()'s C++ throw
+    // started (see rust_upcall.cpp's __gxx_personality_v0): begin_catch
+    // tells the unwinder the exception was actually caught here, and
+    // end_catch releases it once `catch` is done running.
+    let ccx = bcx.ccx();
+    let cxa_begin_catch =
+        get_extern_fn(ccx.externs, ccx.llmod, "__cxa_begin_catch",
+                     lib::llvm::CCallConv,
+                     T_fn([T_ptr(T_i8())], T_ptr(T_i8())));
+    let cxa_end_catch =
+        get_extern_fn(ccx.externs, ccx.llmod, "__cxa_end_catch",
+                     lib::llvm::CCallConv, T_fn([], T_void()));
+    let llexn = ExtractValue(pad_bcx, llretval, 0u);
+    Call(pad_bcx, cxa_begin_catch, [llexn]);
+    let catch_r = catch(pad_bcx);
+    Call(catch_r.bcx, cxa_end_catch, []);
+
+    let body_r = body(try_cx);
+
+    let done_cx = sub_block(bcx, "try-done");
+    Br(body_r.bcx, done_cx.llbb);
+    Br(catch_r.bcx, done_cx.llbb);
+    let llval = Phi(done_cx, val_ty(body_r.val),
+                    [body_r.val, catch_r.val],
+                    [body_r.bcx.llbb, catch_r.bcx.llbb]);
+    ret rslt(done_cx, llval);
+}
+
 fn trans_tup(bcx: block, elts: [@ast::expr], id: ast::node_id,
              dest: dest) -> block {
     let t = node_id_type(bcx, id);
@@ -2874,6 +4027,10 @@ fn trans_rec(bcx: block, fields: [ast::field],
                 let dst = GEP_tup_like(bcx, t, addr, [0, i]);
                 let base = GEP_tup_like(bcx, t, base_val, [0, i]);
                 let val = load_if_immediate(base.bcx, base.val, tf.mt.ty);
+                // INIT rather than DROP_EXISTING: dst is a freshly GEP'd
+                // slot in the record under construction, so it can't yet
+                // hold a live value that might alias base_val, and
+                // copy_val's self-copy guard is skipped accordingly.
                 bcx = copy_val(base.bcx, INIT, dst.val, val, tf.mt.ty);
             }
             i += 1;
@@ -3021,17 +4178,17 @@ fn trans_expr(bcx: block, e: @ast::expr, dest: dest) -> block {
         let fty = node_id_type(bcx, callee_id);
         ret trans_call_inner(bcx, fty, {|bcx|
             impl::trans_method_callee(bcx, callee_id, base, origin)
-        }, [idx], e.id, dest);
+        }, [idx], e.id, dest, none);
       }
 
       // These return nothing
       ast::expr_break {
         assert dest == ignore;
-        ret trans_break(bcx);
+        ret trans_break(e.span, bcx);
       }
       ast::expr_cont {
         assert dest == ignore;
-        ret trans_cont(bcx);
+        ret trans_cont(e.span, bcx);
       }
       ast::expr_ret(ex) {
         assert dest == ignore;
@@ -3048,13 +4205,13 @@ fn trans_expr(bcx: block, e: @ast::expr, dest: dest) -> block {
         assert dest == ignore;
         ret trans_log(lvl, bcx, a);
       }
-      ast::expr_assert(a) {
+      ast::expr_assert(a, msg) {
         assert dest == ignore;
-        ret trans_check_expr(bcx, a, "Assertion");
+        ret trans_check_expr(bcx, a, "Assertion", msg);
       }
       ast::expr_check(ast::checked_expr, a) {
         assert dest == ignore;
-        ret trans_check_expr(bcx, a, "Predicate");
+        ret trans_check_expr(bcx, a, "Predicate", none);
       }
       ast::expr_check(ast::claimed_expr, a) {
         assert dest == ignore;
@@ -3063,12 +4220,15 @@ fn trans_expr(bcx: block, e: @ast::expr, dest: dest) -> block {
            check the value of that variable, doing nothing
            if it's set to false and acting like a check
            otherwise. */
-        let c = get_extern_const(bcx.ccx().externs, bcx.ccx().llmod,
-                                 "check_claims", T_bool());
+        let c = get_runtime_flag_global(bcx.ccx(), "check_claims");
         ret with_cond(bcx, Load(bcx, c)) {|bcx|
-            trans_check_expr(bcx, a, "Claim")
+            trans_check_expr(bcx, a, "Claim", none)
         };
       }
+      ast::expr_asm(a) {
+        assert dest == ignore;
+        ret trans_asm(bcx, a);
+      }
       ast::expr_for(decl, seq, body) {
         assert dest == ignore;
         ret trans_for(bcx, decl, seq, body);
@@ -3097,7 +4257,8 @@ fn trans_expr(bcx: block, e: @ast::expr, dest: dest) -> block {
         let {bcx, val: addr, kind} = trans_lval(src_r.bcx, dst);
         assert kind == owned;
         ret move_val(bcx, DROP_EXISTING, addr, src_r,
-                     expr_ty(bcx, src));
+                     expr_ty(bcx, src),
+                     bcx.ccx().last_uses.contains_key(src.id));
       }
       ast::expr_swap(dst, src) {
         assert dest == ignore;
@@ -3106,10 +4267,13 @@ fn trans_expr(bcx: block, e: @ast::expr, dest: dest) -> block {
         let rhs_res = trans_lval(lhs_res.bcx, src);
         let t = expr_ty(bcx, src);
         let {bcx: bcx, val: tmp_alloc} = alloc_ty(rhs_res.bcx, t);
-        // Swap through a temporary.
-        bcx = move_val(bcx, INIT, tmp_alloc, lhs_res, t);
-        bcx = move_val(bcx, INIT, lhs_res.val, rhs_res, t);
-        ret move_val(bcx, INIT, rhs_res.val, lval_owned(bcx, tmp_alloc), t);
+        // Swap through a temporary. lhs/rhs are still live locals after
+        // this (just with each other's former values), so their slots must
+        // still be zeroed, not have their cleanups revoked.
+        bcx = move_val(bcx, INIT, tmp_alloc, lhs_res, t, false);
+        bcx = move_val(bcx, INIT, lhs_res.val, rhs_res, t, false);
+        ret move_val(bcx, INIT, rhs_res.val, lval_owned(bcx, tmp_alloc), t,
+                     false);
       }
       ast::expr_assign_op(op, dst, src) {
         assert dest == ignore;
@@ -3181,11 +4345,51 @@ fn spill_if_immediate(cx: block, v: ValueRef, t: ty::t) -> result {
     ret rslt(cx, v);
 }
 
+// Already does a genuine Load instruction rather than folding `v` to a
+// constant, so this is TLS-safe as-is for a thread-local's address
+// without any extra handling: LLVM resolves the thread-local addressing
+// for that Load itself, keyed off the GlobalVariable's thread_local flag.
 fn load_if_immediate(cx: block, v: ValueRef, t: ty::t) -> ValueRef {
-    if ty::type_is_immediate(t) { ret Load(cx, v); }
+    if ty::type_is_immediate(t) {
+        let v = Load(cx, v);
+        set_box_pointer_metadata(cx, v, t);
+        ret v;
+    }
     ret v;
 }
 
+// Loading a box (@T) or unique (~T) field hands back a pointer whose
+// pointee's layout is already known statically, so a following autoderef
+// (see autoderef above) doesn't need LLVM to re-derive alignment or prove
+// the dereference is in-bounds on its own: `!align` records the pointee's
+// alignment, and, when the box's allocation size is also statically known,
+// `!dereferenceable` records how many bytes past the pointer are safe to
+// load. Raw pointers (*T) carry no allocation to size, so they're left
+// alone.
+fn set_box_pointer_metadata(cx: block, v: ValueRef, t: ty::t) unsafe {
+    let ccx = cx.ccx();
+    let pointee = alt ty::get(t).struct {
+      ty::ty_box(mt) { some(mt.ty) }
+      ty::ty_uniq(mt) { some(mt.ty) }
+      _ { none }
+    };
+    alt pointee {
+      some(inner) {
+        if check type_has_static_size(ccx, inner) {
+            let llty = type_of(ccx, inner);
+            SetAlignMetadata(v, llalign_of_real(ccx, llty));
+            if ty::type_is_box(t) {
+                SetDereferenceableMetadata(
+                    v, llsize_of_real(ccx, T_box(ccx, llty)));
+            } else {
+                SetDereferenceableMetadata(v, llsize_of_real(ccx, llty));
+            }
+        }
+      }
+      none { }
+    }
+}
+
 fn trans_log(lvl: @ast::expr, bcx: block, e: @ast::expr) -> block {
     let ccx = bcx.ccx();
     if ty::type_is_bot(expr_ty(bcx, lvl)) {
@@ -3231,16 +4435,89 @@ fn trans_log(lvl: @ast::expr, bcx: block, e: @ast::expr) -> block {
     }
 }
 
-fn trans_check_expr(bcx: block, e: @ast::expr, s: str) -> block {
+fn trans_check_expr(bcx: block, e: @ast::expr, s: str,
+                    msg: option<@ast::expr>) -> block {
     let expr_str = s + " " + expr_to_str(e) + " failed";
     let {bcx, val} = with_scope_result(bcx, "check") {|bcx|
         trans_temp_expr(bcx, e)
     };
     with_cond(bcx, Not(bcx, val)) {|bcx|
-        trans_fail(bcx, some(e.span), expr_str)
+        alt msg {
+          none { trans_fail(bcx, some(e.span), expr_str) }
+          some(msg_expr) {
+            let ccx = bcx.ccx(), tcx = ccx.tcx;
+            let msg_res = trans_temp_expr(bcx, msg_expr);
+            let bcx = msg_res.bcx;
+            let data = tvec::get_dataptr(
+                bcx, msg_res.val, type_of_or_i8(
+                    ccx, ty::mk_mach_uint(tcx, ast::ty_u8)));
+            trans_fail_value(bcx, some(e.span), data)
+          }
+        }
     }
 }
 
+// Lowers an `asm!`-style inline assembly expression straight to an LLVM
+// "constant" inline-asm value (there's no separate LLVMBuildInlineAsm in
+// this vintage of the C API -- you build the asm as a callee via
+// LLVMConstInlineAsm, same as add_comment() above, and then Call it).
+// Integer-only operands for now: every input/output slot is ccx.int_type.
+fn trans_asm(bcx: block, a: ast::inline_asm) -> block {
+    let ccx = bcx.ccx();
+    let bcx = bcx;
+
+    let out_ptrs = [];
+    for o in a.outputs {
+        let r = trans_lval(bcx, o.expr);
+        bcx = r.bcx;
+        assert r.kind == owned;
+        out_ptrs += [r.val];
+    }
+
+    let in_vals = [];
+    for i in a.inputs {
+        let r = trans_temp_expr(bcx, i.expr);
+        bcx = r.bcx;
+        in_vals += [r.val];
+    }
+
+    let n_outputs = vec::len(a.outputs);
+    let retty = if n_outputs == 0u {
+        T_void()
+    } else if n_outputs == 1u {
+        ccx.int_type
+    } else {
+        T_struct(vec::init_elt(n_outputs, ccx.int_type))
+    };
+    let argtys = vec::init_elt(vec::len(a.inputs), ccx.int_type);
+    let fn_ty = T_fn(argtys, retty);
+
+    let constraints =
+        str::connect(vec::map(a.outputs, {|o| o.constraint }) +
+                     vec::map(a.inputs, {|i| i.constraint }) +
+                     vec::map(a.clobbers, {|c| "~{" + c + "}" }),
+                     ",");
+    let side_effects = if a.volatile { True } else { False };
+    let asm_val = str::as_buf(a.asm, {|asm_c|
+        str::as_buf(constraints, {|con_c|
+            llvm::LLVMConstInlineAsm(fn_ty, asm_c, con_c, side_effects,
+                                     False)
+        })
+    });
+    let result = Call(bcx, asm_val, in_vals);
+
+    if n_outputs == 1u {
+        Store(bcx, result, out_ptrs[0]);
+    } else {
+        let i = 0u;
+        for ptr in out_ptrs {
+            Store(bcx, ExtractValue(bcx, result, i), ptr);
+            i += 1u;
+        }
+    }
+    ret bcx;
+}
+
 fn trans_fail_expr(bcx: block, sp_opt: option<span>,
                    fail_expr: option<@ast::expr>) -> block {
     let bcx = bcx;
@@ -3279,24 +4556,38 @@ fn trans_fail_value(bcx: block, sp_opt: option<span>,
     let ccx = bcx.ccx();
     let V_filename;
     let V_line;
+    let V_col;
     alt sp_opt {
       some(sp) {
         let sess = bcx.sess();
         let loc = codemap::lookup_char_pos(sess.parse_sess.cm, sp.lo);
         V_filename = C_cstr(bcx.ccx(), loc.file.name);
         V_line = loc.line as int;
+        V_col = loc.col as int;
+      }
+      none {
+        V_filename = C_cstr(bcx.ccx(), "<runtime>");
+        V_line = 0;
+        V_col = 0;
       }
-      none { V_filename = C_cstr(bcx.ccx(), "<runtime>"); V_line = 0; }
     }
     let V_str = PointerCast(bcx, V_fail_str, T_ptr(T_i8()));
     V_filename = PointerCast(bcx, V_filename, T_ptr(T_i8()));
-    let args = [V_str, V_filename, C_int(ccx, V_line)];
+    // Naming the failing function costs a rodata string per call site,
+    // so only bother when the user asked for extra debug info.
+    let V_fn_path = if ccx.sess.opts.debuginfo >= 2u {
+        PointerCast(bcx, C_cstr(ccx, path_str(bcx.fcx.path)), T_ptr(T_i8()))
+    } else {
+        C_null(T_ptr(T_i8()))
+    };
+    let args = [V_str, V_filename, C_int(ccx, V_line), C_int(ccx, V_col),
+               V_fn_path];
     let bcx = invoke(bcx, bcx.ccx().upcalls._fail, args);
     Unreachable(bcx);
     ret bcx;
 }
 
-fn trans_break_cont(bcx: block, to_end: bool)
+fn trans_break_cont(sp: span, bcx: block, to_end: bool)
     -> block {
     // Locate closest loop block, outputting cleanup as we go.
     let unwind = bcx, target = bcx;
@@ -3323,17 +4614,34 @@ fn trans_break_cont(bcx: block, to_end: bool)
           }
         };
     }
+    // Under --structured-cfg, a break/continue that unwinds past its
+    // immediate loop-body scope (e.g. one nested inside an `if` or another
+    // loop) branches straight from deep inside that nesting to an
+    // ancestor's continue/exit block -- a cross-edge a structured (wasm
+    // block/loop/br_if-style) encoding can't represent without a
+    // relooper pass to rebuild the nesting. This tree has no relooper, so
+    // for now such a break/continue is rejected outright rather than
+    // silently emitting a CFG a future wasm lowering couldn't consume. A
+    // break/continue directly in the loop's own body scope is fine: it
+    // targets its immediately-enclosing loop, which is already properly
+    // nested.
+    if bcx.sess().opts.structured_cfg && !box::ptr_eq(unwind, bcx) {
+        bcx.sess().span_unimpl(sp,
+            (if to_end { "break" } else { "cont" }) +
+            " nested inside an if/loop: --structured-cfg has no relooper \
+             to rebuild this as properly nested wasm-style control flow");
+    }
     cleanup_and_Br(bcx, unwind, target.llbb);
     Unreachable(bcx);
     ret bcx;
 }
 
-fn trans_break(cx: block) -> block {
-    ret trans_break_cont(cx, true);
+fn trans_break(sp: span, cx: block) -> block {
+    ret trans_break_cont(sp, cx, true);
 }
 
-fn trans_cont(cx: block) -> block {
-    ret trans_break_cont(cx, false);
+fn trans_cont(sp: span, cx: block) -> block {
+    ret trans_break_cont(sp, cx, false);
 }
 
 fn trans_ret(bcx: block, e: option<@ast::expr>) -> block {
@@ -3390,7 +4698,9 @@ fn init_local(bcx: block, local: @ast::local) -> block {
             bcx = trans_expr_save_in(bcx, init.expr, llptr);
         } else { // This is a move from an lval, must perform an actual move
             let sub = trans_lval(bcx, init.expr);
-            bcx = move_val(sub.bcx, INIT, llptr, sub, ty);
+            bcx = move_val(sub.bcx, INIT, llptr, sub, ty,
+                            bcx.ccx().last_uses.contains_key(
+                                init.expr.id));
         }
       }
       _ { bcx = zero_alloca(bcx, llptr, ty); }
@@ -3404,26 +4714,50 @@ fn zero_alloca(cx: block, llptr: ValueRef, t: ty::t)
     -> block {
     let bcx = cx;
     let ccx = cx.ccx();
+    // Above this many bytes, a `Store(C_null(llty), ...)` of the whole
+    // aggregate at once tends to lower to a pile of individual field
+    // stores that's bigger and slower than just memset-ing the bytes; a
+    // small struct or scalar is cheaper as the single store LLVM already
+    // does well. The cutoff mirrors nothing more precise than "this is a
+    // handful of words, not a real aggregate".
+    const zero_alloca_memset_threshold: uint = 32u;
     if check type_has_static_size(ccx, t) {
         let llty = type_of(ccx, t);
+        if llsize_of_real(ccx, llty) > zero_alloca_memset_threshold {
+            ret call_memset(cx, llptr, llsize_of(ccx, llty),
+                             llalign_of_real(ccx, llty));
+        }
         Store(bcx, C_null(llty), llptr);
     } else {
-        let key = alt ccx.sess.targ_cfg.arch {
-          session::arch_x86 | session::arch_arm { "llvm.memset.p0i8.i32" }
-          session::arch_x86_64 { "llvm.memset.p0i8.i64" }
-        };
-        let i = ccx.intrinsics;
-        let memset = i.get(key);
-        let dst_ptr = PointerCast(cx, llptr, T_ptr(T_i8()));
         let size = size_of(cx, t);
         bcx = size.bcx;
-        let align = C_i32(1i32); // cannot use computed value here.
-        let volatile = C_bool(false);
-        Call(cx, memset, [dst_ptr, C_u8(0u), size.val, align, volatile]);
+        // t is dynamically sized here (the static-size case above uses a
+        // plain Store or a memset with the type's real alignment), so
+        // there's no compile-time alignment to hand LLVM; 1 is the only
+        // sound bound.
+        ret call_memset(bcx, llptr, size.val, 1u);
     }
     ret bcx;
 }
 
+// Shared by both zero_alloca branches: memset `llptr` to zero over `size`
+// bytes at `align`.
+fn call_memset(cx: block, llptr: ValueRef, size: ValueRef,
+               align: uint) -> block {
+    let ccx = cx.ccx();
+    let key = alt ccx.sess.targ_cfg.arch {
+      session::arch_x86 | session::arch_arm { "llvm.memset.p0i8.i32" }
+      session::arch_x86_64 { "llvm.memset.p0i8.i64" }
+    };
+    let i = ccx.intrinsics;
+    let memset = i.get(key);
+    let dst_ptr = PointerCast(cx, llptr, T_ptr(T_i8()));
+    let llalign = C_i32(align as i32);
+    let volatile = C_bool(false);
+    Call(cx, memset, [dst_ptr, C_u8(0u), size, llalign, volatile]);
+    ret cx;
+}
+
 fn trans_stmt(cx: block, s: ast::stmt) -> block {
     #debug["trans_expr(%s)", stmt_to_str(s)];
 
@@ -3443,7 +4777,7 @@ fn trans_stmt(cx: block, s: ast::stmt) -> block {
           ast::decl_local(locals) {
             for local in locals {
                 bcx = init_local(bcx, local);
-                if cx.sess().opts.extra_debuginfo {
+                if cx.sess().opts.debuginfo >= 2u {
                     debuginfo::create_local_var(bcx, local);
                 }
             }
@@ -3462,7 +4796,7 @@ fn trans_stmt(cx: block, s: ast::stmt) -> block {
 fn new_block(cx: fn_ctxt, parent: block_parent, kind: block_kind,
              name: str, block_span: option<span>) -> block {
     let s = "";
-    if cx.ccx.sess.opts.save_temps || cx.ccx.sess.opts.debuginfo {
+    if cx.ccx.sess.opts.save_temps || cx.ccx.sess.opts.debuginfo >= 1u {
         s = cx.ccx.names(name);
     }
     let llbb: BasicBlockRef = str::as_buf(s, {|buf|
@@ -3486,7 +4820,8 @@ fn new_block(cx: fn_ctxt, parent: block_parent, kind: block_kind,
 
 fn simple_block_scope() -> block_kind {
     block_scope({is_loop: none, mutable cleanups: [],
-                 mutable cleanup_paths: [], mutable landing_pad: none})
+                 mutable cleanup_paths: [], mutable landing_pad: none,
+                 mutable lifetime_ends: []})
 }
 
 // Use this when you're at the top block of a function or the like.
@@ -3507,7 +4842,8 @@ fn loop_scope_block(bcx: block, _cont: loop_cont,
         is_loop: some({cnt: _cont, brk: _break}),
         mutable cleanups: [],
         mutable cleanup_paths: [],
-        mutable landing_pad: none
+        mutable landing_pad: none,
+        mutable lifetime_ends: []
     }), n, some(sp));
 }
 
@@ -3540,9 +4876,15 @@ fn trans_block_cleanups(bcx: block, cleanup_cx: block) ->
     if bcx.unreachable { ret bcx; }
     let bcx = bcx;
     alt check cleanup_cx.kind {
-      block_scope({cleanups, _}) {
+      block_scope({cleanups, lifetime_ends, _}) {
         vec::riter(cleanups) {|cu|
-            alt cu { clean(cfn) | clean_temp(_, cfn) { bcx = cfn(bcx); } }
+            alt cu { clean(_, cfn) | clean_temp(_, cfn) { bcx = cfn(bcx); } }
+        }
+        // The locals allocated in this scope are dead now that its
+        // cleanups have run; tell LLVM their stack slots can be reused.
+        for le in lifetime_ends {
+            let (llptr, llty) = le;
+            call_lifetime_intrinsic(bcx, "llvm.lifetime.end", llptr, llty);
         }
       }
     }
@@ -3687,7 +5029,7 @@ fn alloc_local(cx: block, local: @ast::local) -> block {
     let r = alloc_ty(cx, t);
     alt p.node {
       ast::pat_ident(pth, none) {
-        if cx.sess().opts.debuginfo {
+        if cx.sess().opts.debuginfo >= 1u {
             let _: () = str::as_buf(path_to_ident(pth), {|buf|
                 llvm::LLVMSetValueName(r.val, buf)
             });
@@ -3695,6 +5037,17 @@ fn alloc_local(cx: block, local: @ast::local) -> block {
       }
       _ { }
     }
+    // Tell LLVM this slot's lifetime starts here, and note it so
+    // trans_block_cleanups can end it when this scope's cleanups run.
+    // Statically-sized only; the dynastack_alloca path in alloc_ty isn't a
+    // plain alloca and isn't what the lifetime intrinsics target.
+    if type_has_static_size(ccx, t) {
+        let llty = type_of(ccx, t);
+        call_lifetime_intrinsic(r.bcx, "llvm.lifetime.start", r.val, llty);
+        in_scope_cx(r.bcx) {|info|
+            info.lifetime_ends += [(r.val, llty)];
+        }
+    }
     cx.fcx.lllocals.insert(local.node.id, local_mem(r.val));
     ret r.bcx;
 }
@@ -3767,6 +5120,7 @@ fn new_fn_ctxt_w_id(ccx: crate_ctxt, path: path,
           llupvars: new_int_hash::<ValueRef>(),
           mutable lltyparams: [],
           derived_tydescs: ty::new_ty_hash(),
+          size_align_metrics: ty::new_ty_hash(),
           id: id,
           param_substs: param_substs,
           span: sp,
@@ -3786,6 +5140,20 @@ fn new_fn_ctxt(ccx: crate_ctxt, path: path, llfndecl: ValueRef,
 //  - new_fn_ctxt
 //  - trans_args
 
+// It'd be nice for a scalar-typed parameter or return value -- always
+// fully initialized, unlike an aggregate or an opaque generic one -- to
+// carry LLVM's `noundef` here and in new_fn_ctxt_w_id's llretptr/param
+// setup, so the optimizer doesn't have to conservatively allow undef to
+// flow through them. But `noundef` is a parameter/return *attribute*
+// bit that postdates this tree's LLVM: lib::llvm::Attribute (see
+// lib/llvm.rs) is the fixed bitmask enum from the LLVMAddAttribute-era
+// C API, which stops at NonLazyBindAttribute and has no bit for it, and
+// there's no separate binding (the way LLVMAddInstrAttribute is separate
+// from LLVMAddFunctionAttr) for the newer attribute-list API that
+// introduced `noundef`. Marking parameters here would mean fabricating
+// an attribute value this LLVM doesn't define, so this stays unimplemented
+// pending a newer LLVM binding.
+//
 // create_llargs_for_fn_args: Creates a mapping from incoming arguments to
 // allocas created for them.
 //
@@ -3793,6 +5161,25 @@ fn new_fn_ctxt(ccx: crate_ctxt, path: path, llfndecl: ValueRef,
 // spaces that have been created for them (by code in the llallocas field of
 // the function's fn_ctxt).  create_llargs_for_fn_args populates the llargs
 // field of the fn_ctxt with
+// A #[restrict] marker on a by-ref pointer arg, generating scoped
+// !alias.scope/!noalias metadata on the loads/stores through it (mirroring
+// how SetAlignMetadata/SetDereferenceableMetadata in trans::build already
+// tag loads through a pointer with facts the optimizer can't otherwise
+// derive), is the natural way to unblock auto-vectorizing something like a
+// SAXPY loop with multiple non-aliasing array parameters. It doesn't fit
+// this tree, though: `ast::arg` (syntax::ast) is `{mode, ty, ident, id}`
+// with no attrs field at all, and parse_arg (syntax::parse::parser) never
+// looks for a leading `#[...]` before an argument -- every other
+// #[attribute] in this dialect (#[linkage], #[thread_local],
+// #[assert_size], #[runtime_flag], ...) decorates a whole item, not one
+// argument of one. Adding per-argument attributes would mean extending
+// ast::arg and its parser, plus every visitor that walks a fn_decl's
+// inputs (resolve, ty, trans, metadata encode/decode, pprust) to carry the
+// new field through -- a grammar change, not a trans-layer one, and too
+// large a surface to bolt on under a single function's fix. The metadata
+// side (LLVMMDNode + a "llvm.loop.parallel_accesses"-style scope list) is
+// straightforward once an attribute reaches here; it's getting the
+// attribute here at all that's blocked.
 fn create_llargs_for_fn_args(cx: fn_ctxt, ty_self: self_arg,
                              args: [ast::arg], ty_params: [ast::ty_param]) {
     // Skip the implicit arguments 0, and 1.
@@ -3863,7 +5250,7 @@ fn copy_args_to_allocas(fcx: fn_ctxt, bcx: block, args: [ast::arg],
           }
           ast::by_ref {}
         }
-        if fcx.ccx.sess.opts.extra_debuginfo {
+        if fcx.ccx.sess.opts.debuginfo >= 2u {
             debuginfo::create_arg(bcx, args[arg_n], args[arg_n].ty.span);
         }
         arg_n += 1u;
@@ -3871,11 +5258,58 @@ fn copy_args_to_allocas(fcx: fn_ctxt, bcx: block, args: [ast::arg],
     ret bcx;
 }
 
+// A crate that implements its own __cyg_profile_func_enter/
+// __cyg_profile_func_exit (rather than linking a profiler that supplies
+// them) and compiles that implementation with --instrument-functions along
+// with everything else would recurse into itself infinitely at every call
+// -- gcc's own -finstrument-functions has the identical hazard, and expects
+// such definitions to live in a translation unit built without the flag.
+// This tree has only a single crate-wide flag, not a per-function opt-out
+// attribute, so instrument_functions_should_skip is the one place that
+// special-cases these two names by their final (post-mangling, post-
+// #[no_mangle]) symbol, the same way is_unpredictable_callee special-cases
+// a callee by name elsewhere in this file.
+fn instrument_functions_should_skip(fcx: fn_ctxt) -> bool {
+    let name = unsafe { str::from_cstr(llvm::LLVMGetValueName(fcx.llfn)) };
+    name == "__cyg_profile_func_enter" || name == "__cyg_profile_func_exit"
+}
+
+// Declares (or reuses the cached extern for) one of the gcc-compatible
+// __cyg_profile_func_enter/__cyg_profile_func_exit hooks, both
+// `void(void *this_fn, void *call_site)`, and calls it with `fcx.llfn`
+// (bitcast to i8*) as this_fn. This tree has no llvm.returnaddress-style
+// binding to recover a genuine caller address for call_site, so a null
+// pointer is passed there instead -- external profilers keyed on this_fn
+// alone (the common case) are unaffected, but anything that inspects
+// call_site will see it as always unknown.
+fn trans_instrument_call(bcx: block, name: str) {
+    let ccx = bcx.fcx.ccx;
+    let void_ptr_ty = T_ptr(T_i8());
+    let llty = T_fn([void_ptr_ty, void_ptr_ty], T_void());
+    let llfn = get_extern_fn(ccx.externs, ccx.llmod, name, lib::llvm::CCallConv,
+                             llty);
+    let this_fn = PointerCast(bcx, bcx.fcx.llfn, void_ptr_ty);
+    let call_site = C_null(void_ptr_ty);
+    Call(bcx, llfn, [this_fn, call_site]);
+}
+
+fn maybe_trans_instrument_enter(fcx: fn_ctxt) {
+    if fcx.ccx.sess.opts.instrument_functions &&
+       !instrument_functions_should_skip(fcx) {
+        trans_instrument_call(raw_block(fcx, fcx.llstaticallocas),
+                              "__cyg_profile_func_enter");
+    }
+}
+
 // Ties up the llstaticallocas -> llloadenv -> llderivedtydescs ->
 // lldynamicallocas -> lltop edges, and builds the return block.
 fn finish_fn(fcx: fn_ctxt, lltop: BasicBlockRef) {
     tie_up_header_blocks(fcx, lltop);
     let ret_cx = raw_block(fcx, fcx.llreturn);
+    if fcx.ccx.sess.opts.instrument_functions &&
+       !instrument_functions_should_skip(fcx) {
+        trans_instrument_call(ret_cx, "__cyg_profile_func_exit");
+    }
     trans_fn_cleanups(fcx, ret_cx);
     RetVoid(ret_cx);
 }
@@ -3896,7 +5330,9 @@ fn trans_closure(ccx: crate_ctxt, path: path, decl: ast::fn_decl,
                  body: ast::blk, llfndecl: ValueRef,
                  ty_self: self_arg, ty_params: [ast::ty_param],
                  param_substs: option<param_substs>,
-                 id: ast::node_id, maybe_load_env: fn(fn_ctxt)) {
+                 id: ast::node_id,
+                 maybe_emit_prologue: fn(fn_ctxt),
+                 maybe_load_env: fn(fn_ctxt)) {
     set_uwtable(llfndecl);
 
     // Set up arguments to the function.
@@ -3910,6 +5346,16 @@ fn trans_closure(ccx: crate_ctxt, path: path, decl: ast::fn_decl,
     let lltop = bcx.llbb;
     let block_ty = node_id_type(bcx, body.node.id);
 
+    // Gives runtime-support code (not ordinary Rust fns -- there's no
+    // surface syntax for this) a place to emit a handful of custom setup
+    // instructions at function entry, ahead of argument copying, without
+    // the full restrictions #[naked] would otherwise impose (normal
+    // locals, cleanups, and the rest of trans_closure still apply). Every
+    // call site passes maybe_trans_instrument_enter, which is a real,
+    // non-no-op prologue under --instrument-functions (see
+    // instrument-functions.rs) and a no-op otherwise.
+    maybe_emit_prologue(fcx);
+
     let arg_tys = ty::ty_fn_args(node_id_type(bcx, id));
     bcx = copy_args_to_allocas(fcx, bcx, decl.inputs, arg_tys);
 
@@ -3942,8 +5388,9 @@ fn trans_fn(ccx: crate_ctxt, path: path, decl: ast::fn_decl,
     let start = if do_time { time::get_time() }
                 else { {sec: 0u32, usec: 0u32} };
     trans_closure(ccx, path, decl, body, llfndecl, ty_self,
-                  ty_params, param_substs, id, {|fcx|
-        if ccx.sess.opts.extra_debuginfo {
+                  ty_params, param_substs, id, maybe_trans_instrument_enter,
+                  {|fcx|
+        if ccx.sess.opts.debuginfo >= 2u {
             debuginfo::create_function(fcx);
         }
     });
@@ -3977,7 +5424,8 @@ fn trans_res_ctor(ccx: crate_ctxt, path: path, dtor: ast::fn_decl,
     }
 
     let {bcx, val: dst} = GEP_tup_like(bcx, tup_t, llretptr, [0, 1]);
-    bcx = memmove_ty(bcx, dst, arg, arg_t);
+    // dst is derived from the return pointer, which can't alias arg.
+    bcx = memcpy_ty(bcx, dst, arg, arg_t);
     let flag = GEP_tup_like(bcx, tup_t, llretptr, [0, 0]);
     bcx = flag.bcx;
     let one = C_u8(1u);
@@ -4056,6 +5504,23 @@ fn trans_enum_variant(ccx: crate_ctxt, enum_id: ast::node_id,
 // FIXME: this should do some structural hash-consing to avoid
 // duplicate constants. I think. Maybe LLVM has a magical mode
 // that does so later on?
+// Folding `size_of::<T>() * n` (or any other size_of-derived expression)
+// into a single compile-time constant, so it can back an array length or
+// another const, doesn't fit this function as it stands. trans_const_expr
+// is a shallow syntactic translator -- expr_lit straight to an LLVM
+// constant, expr_binary/expr_unary recursing into already-folded operands
+// -- with no expr_call arm at all: it has no notion of "call a function"
+// to fold through. And size_of itself (core::sys::size_of, see
+// libcore/sys.rs) isn't a rust_intrinsic the way get_type_desc it calls
+// into is; it's an ordinary generic fn that dereferences a runtime
+// %struct.type_desc* and reads its `size` field. Folding a call to it here
+// would mean either hardcoding recognition of that one specific library
+// function by name/def_id -- a by-name special case like
+// is_unpredictable_callee's, rather than the #[abi=...] extension point
+// alone -- or
+// growing trans_const_expr into a real partial evaluator able to inline
+// and execute arbitrary generic function bodies at compile time. Neither
+// is a small addition on top of the existing literal/binop/unop folder.
 fn trans_const_expr(cx: crate_ctxt, e: @ast::expr) -> ValueRef {
     alt e.node {
       ast::expr_lit(lit) { ret trans_crate_lit(cx, *lit); }
@@ -4196,8 +5661,19 @@ fn trans_item(ccx: crate_ctxt, item: ast::item) {
             }
             i += 1;
         }
+        if ccx.sess.opts.debuginfo >= 2u && tps.len() == 0u {
+            debuginfo::create_enum_metadata(
+                ccx, ty::node_id_to_type(ccx.tcx, item.id), item);
+        }
+      }
+      ast::item_const(_, expr) {
+        // A #[runtime_flag] const's global is declared but never defined
+        // here (see collect_item) -- it's up to the runtime to give it a
+        // value, so there's no initializer expression to translate.
+        if !attr::attrs_contains_name(item.attrs, "runtime_flag") {
+            trans_const(ccx, expr, item.id);
+        }
       }
-      ast::item_const(_, expr) { trans_const(ccx, expr, item.id); }
       ast::item_native_mod(native_mod) {
         let abi = alt attr::native_abi(item.attrs) {
           either::right(abi_) { abi_ }
@@ -4205,10 +5681,50 @@ fn trans_item(ccx: crate_ctxt, item: ast::item) {
         };
         native::trans_native_mod(ccx, native_mod, abi);
       }
+      ast::item_ty(_, _) {
+        check_assert_size(ccx, item);
+      }
       _ {/* fall through */ }
     }
 }
 
+// Checks a #[assert_size(N)] attribute on a type item against the type's
+// real layout, so layout drift is caught at build time rather than at the
+// FFI boundary.
+fn check_assert_size(ccx: crate_ctxt, item: ast::item) {
+    let metas = attr::find_attrs_by_name(item.attrs, "assert_size");
+    for attr in metas {
+        let n = alt attr::attr_meta(attr).node {
+          ast::meta_name_value(_, {node: ast::lit_int(i, _), _}) {
+            i as uint
+          }
+          ast::meta_name_value(_, {node: ast::lit_uint(u, _), _}) {
+            u as uint
+          }
+          _ {
+            ccx.sess.span_fatal(item.span,
+                                "assert_size expects an integer, e.g. \
+                                 #[assert_size = 8]");
+          }
+        };
+        let t = ty::lookup_item_type(ccx.tcx, local_def(item.id)).ty;
+        if check type_has_static_size(ccx, t) {
+            let llty = type_of(ccx, t);
+            let actual = llsize_of_real(ccx, llty);
+            if actual != n {
+                ccx.sess.span_fatal(item.span,
+                    #fmt["type %s has size %u, but is declared \
+                          #[assert_size = %u]",
+                         ty_to_str(ccx.tcx, t), actual, n]);
+            }
+        } else {
+            ccx.sess.span_fatal(item.span,
+                "assert_size cannot be applied to a dynamically sized \
+                 type");
+        }
+    }
+}
+
 // Translate a module. Doing this amounts to translating the items in the
 // module; there ends up being no artifact (aside from linkage names) of
 // separate modules in the compiled program.  That's because modules exist
@@ -4223,31 +5739,186 @@ fn get_pair_fn_ty(llpairty: TypeRef) -> TypeRef {
     ret struct_elt(llpairty, 0u);
 }
 
+// Reads a #[linkage = "..."] attribute, if present, and validates it
+// against the linkage kinds we're willing to hand out explicit control
+// over. Used for both functions (register_fn_fuller) and statics
+// (collect_item's item_const arm).
+fn item_linkage(sess: session, sp: span, attrs: [ast::attribute]) ->
+   option<lib::llvm::Linkage> {
+    alt attr::get_meta_item_value_str_by_name(attrs, "linkage") {
+      option::none { option::none }
+      option::some("internal") { option::some(lib::llvm::InternalLinkage) }
+      option::some("external") { option::some(lib::llvm::ExternalLinkage) }
+      option::some("weak") { option::some(lib::llvm::WeakAnyLinkage) }
+      option::some("linkonce") {
+        option::some(lib::llvm::LinkOnceAnyLinkage)
+      }
+      option::some("appending") { option::some(lib::llvm::AppendingLinkage) }
+      option::some(l) {
+        sess.span_fatal(sp, "unrecognized linkage `" + l + "`; expected \
+                             one of `internal`, `external`, `weak`, \
+                             `linkonce`, `appending`");
+      }
+    }
+}
+
+// Reads a #[section = "..."] attribute, if present, and validates that it
+// names a plain string (as opposed to a bare `#[section]` or a list form
+// like `#[section(foo)]`, neither of which say what section to use).
+// Composes with item_linkage since both just set independent properties
+// on the same emitted global -- an exported #[section]'d static still
+// gets whatever linkage its own #[linkage] (or lack of one) asks for.
+fn item_section(sess: session, sp: span, attrs: [ast::attribute]) ->
+   option<str> {
+    if !attr::attrs_contains_name(attrs, "section") { ret option::none; }
+    alt attr::get_meta_item_value_str_by_name(attrs, "section") {
+      option::some(s) { option::some(s) }
+      option::none {
+        sess.span_fatal(sp, "section must be a string, e.g. \
+                             #[section = \".mytext\"]");
+      }
+    }
+}
+
+// A top-down walk from the crate root mirroring metadata::encoder's
+// encode_module_item_paths and rustdoc::prune_unexported_pass's fold_mod:
+// both independently re-derive "is this item part of the crate's public
+// surface" the same way, by only recursing into a submodule once
+// ast_util::is_exported says the submodule itself is visible. This builds
+// the same set once for trans's own use in register_fn_fuller.
+fn compute_reachable_items(crate: @ast::crate) -> hashmap<ast::node_id, ()> {
+    let reachable = new_int_hash::<()>();
+    fn walk_mod(reachable: hashmap<ast::node_id, ()>, m: ast::_mod) {
+        for it: @ast::item in m.items {
+            if !ast_util::is_exported(it.ident, m) { cont; }
+            reachable.insert(it.id, ());
+            alt it.node {
+              ast::item_mod(sub) { walk_mod(reachable, sub); }
+              ast::item_native_mod(nmod) {
+                for nit in nmod.items { reachable.insert(nit.id, ()); }
+              }
+              ast::item_enum(variants, _) {
+                for variant in variants {
+                    if ast_util::is_exported(variant.node.name, m) {
+                        reachable.insert(variant.node.id, ());
+                    }
+                }
+              }
+              ast::item_impl(_, _, _, methods) {
+                // A method isn't independently exportable in this
+                // dialect -- only the impl item itself is named in an
+                // `export` list -- so every method of an exported impl
+                // is reachable along with it.
+                for m in methods { reachable.insert(m.id, ()); }
+              }
+              ast::item_res(_, _, _, _, ctor_id) {
+                reachable.insert(ctor_id, ());
+              }
+              _ { }
+            }
+        }
+    }
+    walk_mod(reachable, crate.node.module);
+    ret reachable;
+}
+
 fn register_fn(ccx: crate_ctxt, sp: span, path: path, flav: str,
-               ty_params: [ast::ty_param], node_id: ast::node_id) {
+               ty_params: [ast::ty_param], node_id: ast::node_id,
+               attrs: [ast::attribute]) {
     let t = ty::node_id_to_type(ccx.tcx, node_id);
-    register_fn_full(ccx, sp, path, flav, ty_params, node_id, t);
+    register_fn_full(ccx, sp, path, flav, ty_params, node_id, t, attrs);
 }
 
 fn param_bounds(ccx: crate_ctxt, tp: ast::ty_param) -> ty::param_bounds {
     ccx.tcx.ty_param_bounds.get(tp.id)
 }
 
+// Maps a #[abi = "..."] on an ordinary Rust fn item (as opposed to a
+// native mod's ABI, which selects how we call out) to the LLVM calling
+// convention its declaration and every direct call site should agree on.
+fn fn_call_conv(attrs: [ast::attribute]) -> lib::llvm::CallConv {
+    alt attr::find_fn_call_conv(attrs) {
+      attr::fcc_rust { lib::llvm::CCallConv }
+      attr::fcc_fastcall { lib::llvm::X86FastcallCallConv }
+      attr::fcc_stdcall { lib::llvm::X86StdcallCallConv }
+    }
+}
+
 fn register_fn_full(ccx: crate_ctxt, sp: span, path: path, flav: str,
                     tps: [ast::ty_param], node_id: ast::node_id,
-                    node_type: ty::t) {
+                    node_type: ty::t, attrs: [ast::attribute]) {
     let llfty = type_of_fn_from_ty(ccx, node_type,
                                    vec::map(tps, {|p| param_bounds(ccx, p)}));
     register_fn_fuller(ccx, sp, path, flav, node_id, node_type,
-                       lib::llvm::CCallConv, llfty);
+                       fn_call_conv(attrs), llfty, attrs);
 }
 
 fn register_fn_fuller(ccx: crate_ctxt, sp: span, path: path, _flav: str,
                       node_id: ast::node_id, node_type: ty::t,
-                      cc: lib::llvm::CallConv, llfty: TypeRef) {
-    let ps: str = mangle_exported_name(ccx, path, node_type);
+                      cc: lib::llvm::CallConv, llfty: TypeRef,
+                      attrs: [ast::attribute]) {
+    let ps: str = if attr::attrs_contains_name(attrs, "no_mangle") {
+        let name = alt vec::last(path) {
+          some(path_name(s)) | some(path_mod(s)) { s }
+          none { ccx.sess.span_bug(sp, "no_mangle on a fn with no name") }
+        };
+        alt ccx.no_mangle_symbols.find(name) {
+          some(prev_sp) {
+            ccx.sess.span_fatal(sp,
+                #fmt["symbol `%s` is exported #[no_mangle] more than once \
+                     (previous definition at %s)",
+                     name, codemap::span_to_str(prev_sp, ccx.sess.codemap)]);
+          }
+          none { ccx.no_mangle_symbols.insert(name, sp); }
+        }
+        name
+    } else {
+        mangle_exported_name(ccx, path, node_type)
+    };
     let llfn: ValueRef = decl_fn(ccx.llmod, ps, cc, llfty);
     ccx.item_ids.insert(node_id, llfn);
+    ccx.item_ccs.insert(node_id, cc);
+    let linkage = item_linkage(ccx.sess, sp, attrs);
+    alt linkage {
+      option::some(l) { lib::llvm::SetLinkage(llfn, l); }
+      option::none {
+        if attr::attrs_contains_name(attrs, "no_mangle") {
+            lib::llvm::SetLinkage(llfn, lib::llvm::ExternalLinkage);
+        } else if !ccx.reachable_items.contains_key(node_id) {
+            // Not part of the crate's public surface and not asking for
+            // a specific linkage of its own: give it internal linkage so
+            // an unused one can actually be dropped by a
+            // --gc-sections-style link, the same way a generic fn's
+            // monomorphized copies already get via
+            // decl_internal_cdecl_fn above.
+            lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
+        }
+      }
+    }
+
+    let inline = attr::find_inline_attr(attrs);
+    alt inline {
+      attr::il_hint { set_inline_hint(llfn); }
+      attr::il_always { set_always_inline(llfn); }
+      attr::il_never { set_no_inline(llfn); }
+      attr::il_none { }
+    }
+    set_instruction_set(ccx, sp, llfn, attr::find_instruction_set_attr(attrs));
+    if attr::attrs_contains_name(attrs, "ifunc") {
+        set_ifunc(ccx, sp, llfn);
+    }
+    // A #[inline]'d fn only helps a downstream crate if that crate
+    // actually has a body to inline; since llfn keeps its default
+    // (external) linkage otherwise, give it linkonce_odr instead so every
+    // crate that pulls in a copy doesn't collide with the others at link
+    // time, the same problem generic glue functions solve by sharing one
+    // definition per identical shape (see get_shape_glue). Skip this if
+    // the item already asked for a specific #[linkage] -- that request
+    // wins.
+    if linkage == option::none &&
+       (inline == attr::il_hint || inline == attr::il_always) {
+        lib::llvm::SetLinkage(llfn, lib::llvm::LinkOnceODRLinkage);
+    }
     ccx.item_symbols.insert(node_id, ps);
 
     let is_main = is_main_name(path) && !ccx.sess.building_library;
@@ -4379,6 +6050,33 @@ fn collect_native_item(ccx: crate_ctxt,
           ast::native_abi_rust_intrinsic {
             // For intrinsics: link the function directly to the intrinsic
             // function itself.
+            //
+            // A `checked_div::<T>(a: T, b: T) -> option<T>` reusing
+            // trans_eager_binop's ast::div/ast::rem arms (above, in this
+            // file) as a guarded rather than trapping division would be a
+            // natural rust-intrinsic here, generic the same way
+            // get_type_desc<T>/memmove<T> already are (see rusti in
+            // core::sys). It doesn't fit this mechanism, though, for two
+            // independent reasons: every existing generic intrinsic's
+            // hand-written body (src/rt/intrinsics/intrinsics.*.ll.in)
+            // only ever reads a type_desc's size/align/glue-pointer
+            // fields, generic over layout, never over the *kind* of a
+            // type -- there's no field to test to pick SDiv/UDiv or the
+            // signed-overflow sentinel (T::min_value) generically, unlike
+            // ordinary trans_eager_binop which has ty::type_is_signed(t)
+            // available at compile time for the one concrete instantiation
+            // it's translating. And its `option<T>` return would need a
+            // tagged-union layout sized and discriminated the way this
+            // compiler's own enum trans (type_of_enum, trans_enum_variant)
+            // builds one for a *specific* T -- not something a single
+            // fixed .ll leaf function, compiled once for every T, can lay
+            // out generically either. Both gaps trace back to the same
+            // root cause dynastack_alloca's rust-intrinsic note above
+            // (search stack_alloc in this file) points at from a different
+            // angle: this mechanism only supports self-contained leaf
+            // functions with no compile-time knowledge of the specific T
+            // (or, there, the calling function's own state) they're
+            // instantiated for.
             let fn_type = type_of_fn_from_ty(
                 ccx, node_type,
                 vec::map(tps, {|p| param_bounds(ccx, p)}));
@@ -4397,7 +6095,7 @@ fn collect_native_item(ccx: crate_ctxt,
             let path = *alt check ccx.tcx.items.get(i.id) {
               ast_map::node_native_item(_, p) { p }
             } + [path_name(i.ident)];
-            register_fn(ccx, i.span, path, "native fn", tps, i.id);
+            register_fn(ccx, i.span, path, "native fn", tps, i.id, i.attrs);
           }
         }
       }
@@ -4417,12 +6115,42 @@ fn collect_item(ccx: crate_ctxt, abi: @mutable option<ast::native_abi>,
     alt i.node {
       ast::item_const(_, _) {
         let typ = ty::node_id_to_type(ccx.tcx, i.id);
-        let s = mangle_exported_name(ccx, my_path, typ);
-        let g = str::as_buf(s, {|buf|
-            llvm::LLVMAddGlobal(ccx.llmod, type_of(ccx, typ), buf)
-        });
-        ccx.item_symbols.insert(i.id, s);
-        ccx.consts.insert(i.id, g);
+        alt attr::get_meta_item_value_str_by_name(i.attrs, "runtime_flag") {
+          option::some(name) {
+            // A #[runtime_flag] const is just a named handle onto the same
+            // kind of RTS-settable global check_claims already reads --
+            // point it at get_runtime_flag_global instead of giving it its
+            // own mangled name and initializer, so ordinary references to
+            // it Load from the global the runtime is expected to flip.
+            if !ty::type_is_bool(typ) {
+                ccx.sess.span_fatal(i.span,
+                    "#[runtime_flag] const must have type bool");
+            }
+            let g = get_runtime_flag_global(ccx, name);
+            ccx.consts.insert(i.id, g);
+          }
+          option::none {
+            let s = mangle_exported_name(ccx, my_path, typ);
+            let g = str::as_buf(s, {|buf|
+                llvm::LLVMAddGlobal(ccx.llmod, type_of(ccx, typ), buf)
+            });
+            alt item_linkage(ccx.sess, i.span, i.attrs) {
+              option::some(linkage) { lib::llvm::SetLinkage(g, linkage); }
+              option::none { }
+            }
+            alt item_section(ccx.sess, i.span, i.attrs) {
+              option::some(section) {
+                str::as_buf(section, {|buf| llvm::LLVMSetSection(g, buf) });
+              }
+              option::none { }
+            }
+            if attr::attrs_contains_name(i.attrs, "thread_local") {
+                llvm::LLVMSetThreadLocal(g, True);
+            }
+            ccx.item_symbols.insert(i.id, s);
+            ccx.consts.insert(i.id, g);
+          }
+        }
       }
       ast::item_native_mod(native_mod) {
         // Propagate the native ABI down to collect_native_item(),
@@ -4434,9 +6162,9 @@ fn collect_item(ccx: crate_ctxt, abi: @mutable option<ast::native_abi>,
       ast::item_fn(decl, tps, _) {
         if decl.purity != ast::crust_fn {
             register_fn(ccx, i.span, my_path, "fn", tps,
-                        i.id);
+                        i.id, i.attrs);
         } else {
-            native::register_crust_fn(ccx, i.span, my_path, i.id);
+            native::register_crust_fn(ccx, i.span, my_path, i.id, i.attrs);
         }
       }
       ast::item_impl(tps, _, _, methods) {
@@ -4444,25 +6172,25 @@ fn collect_item(ccx: crate_ctxt, abi: @mutable option<ast::native_abi>,
         for m in methods {
             register_fn(ccx, i.span,
                         path + [path_name(m.ident)],
-                        "impl_method", tps + m.tps, m.id);
+                        "impl_method", tps + m.tps, m.id, m.attrs);
         }
       }
       ast::item_res(_, tps, _, dtor_id, ctor_id) {
-        register_fn(ccx, i.span, my_path, "res_ctor", tps, ctor_id);
+        register_fn(ccx, i.span, my_path, "res_ctor", tps, ctor_id, i.attrs);
         // Note that the destructor is associated with the item's id, not
         // the dtor_id. This is a bit counter-intuitive, but simplifies
         // ty_res, which would have to carry around two def_ids otherwise
         // -- one to identify the type, and one to find the dtor symbol.
         let t = ty::node_id_to_type(ccx.tcx, dtor_id);
         register_fn_full(ccx, i.span, my_path + [path_name("dtor")],
-                         "res_dtor", tps, i.id, t);
+                         "res_dtor", tps, i.id, t, i.attrs);
       }
       ast::item_enum(variants, tps) {
         for variant in variants {
             if variant.node.args.len() != 0u {
                 register_fn(ccx, i.span,
                             my_path + [path_name(variant.node.name)],
-                            "enum", tps, variant.node.id);
+                            "enum", tps, variant.node.id, variant.node.attrs);
             }
         }
       }
@@ -4554,6 +6282,12 @@ fn declare_intrinsics(llmod: ModuleRef) -> hashmap<str, ValueRef> {
     let memmove64 =
         decl_cdecl_fn(llmod, "llvm.memmove.p0i8.p0i8.i64",
                       T_fn(T_memmove64_args, T_void()));
+    let memcpy32 =
+        decl_cdecl_fn(llmod, "llvm.memcpy.p0i8.p0i8.i32",
+                      T_fn(T_memmove32_args, T_void()));
+    let memcpy64 =
+        decl_cdecl_fn(llmod, "llvm.memcpy.p0i8.p0i8.i64",
+                      T_fn(T_memmove64_args, T_void()));
     let memset32 =
         decl_cdecl_fn(llmod, "llvm.memset.p0i8.i32",
                       T_fn(T_memset32_args, T_void()));
@@ -4561,17 +6295,98 @@ fn declare_intrinsics(llmod: ModuleRef) -> hashmap<str, ValueRef> {
         decl_cdecl_fn(llmod, "llvm.memset.p0i8.i64",
                       T_fn(T_memset64_args, T_void()));
     let trap = decl_cdecl_fn(llmod, "llvm.trap", T_fn(T_trap_args, T_void()));
+    let T_lifetime_args: [TypeRef] = [T_i64(), T_ptr(T_i8())];
+    let lifetime_start =
+        decl_cdecl_fn(llmod, "llvm.lifetime.start",
+                      T_fn(T_lifetime_args, T_void()));
+    let lifetime_end =
+        decl_cdecl_fn(llmod, "llvm.lifetime.end",
+                      T_fn(T_lifetime_args, T_void()));
+    // Used by trans_closure_trampoline below to bake a closure's
+    // environment pointer into a small stack-resident thunk, so the
+    // resulting bare function pointer can be handed to a C callback
+    // parameter.
+    let init_trampoline =
+        decl_cdecl_fn(llmod, "llvm.init.trampoline",
+                      T_fn([T_ptr(T_i8()), T_ptr(T_i8()), T_ptr(T_i8())],
+                           T_void()));
+    let adjust_trampoline =
+        decl_cdecl_fn(llmod, "llvm.adjust.trampoline",
+                      T_fn([T_ptr(T_i8())], T_ptr(T_i8())));
     let intrinsics = new_str_hash::<ValueRef>();
     intrinsics.insert("llvm.gcroot", gcroot);
     intrinsics.insert("llvm.gcread", gcread);
     intrinsics.insert("llvm.memmove.p0i8.p0i8.i32", memmove32);
     intrinsics.insert("llvm.memmove.p0i8.p0i8.i64", memmove64);
+    intrinsics.insert("llvm.memcpy.p0i8.p0i8.i32", memcpy32);
+    intrinsics.insert("llvm.memcpy.p0i8.p0i8.i64", memcpy64);
     intrinsics.insert("llvm.memset.p0i8.i32", memset32);
     intrinsics.insert("llvm.memset.p0i8.i64", memset64);
     intrinsics.insert("llvm.trap", trap);
+    intrinsics.insert("llvm.lifetime.start", lifetime_start);
+    intrinsics.insert("llvm.lifetime.end", lifetime_end);
+    intrinsics.insert("llvm.init.trampoline", init_trampoline);
+    intrinsics.insert("llvm.adjust.trampoline", adjust_trampoline);
     ret intrinsics;
 }
 
+// The trampoline buffer LLVM writes the thunk into. The x86/x86_64
+// backends need at most 32 bytes; using the same size/alignment on every
+// target keeps this one call site simple rather than switching on
+// targ_cfg::arch here too.
+const trampoline_buf_sz: uint = 32u;
+const trampoline_buf_align: uint = 16u;
+
+// Bridges a Rust closure (see create_real_fn_pair/fill_fn_pair above: a
+// `code` function whose second parameter is always its environment
+// pointer, and a matching `llenvptr`) to a bare, C-callable function
+// pointer of type `llcallee_ty`, for the common "pass a capturing closure
+// where a callback expecting a plain function pointer is required"
+// pattern. This works by writing a small thunk into a stack buffer via
+// llvm.init.trampoline, which bakes `llenvptr` into the thunk so calling
+// it invokes `llfn` with that environment already supplied; the `nest`
+// attribute on `llfn`'s environment parameter is what tells LLVM which
+// parameter the trampoline should bind (ordinary direct calls to `llfn`,
+// e.g. through its usual fn pair, are unaffected -- `nest` only changes
+// how llvm.init.trampoline lowers, not llfn's calling convention).
+//
+// The returned pointer is only valid for the lifetime of `bcx`'s stack
+// frame, exactly like returning a pointer to a local: calling it after
+// the frame that created it has returned is undefined behaviour.
+//
+// NOT YET USABLE: there's no syntax in this tree yet that calls this, and
+// the original request's ask -- a test passing an environment-capturing
+// closure to a C function expecting a bare callback and observing the
+// captured value -- can't be exercised, because nothing produces the
+// concrete `llfn`/`llenvptr` pair this function needs. Unlike every
+// existing `rust-intrinsic`, which trans emits as an ordinary call
+// resolved against a symbol in src/rt/intrinsics/intrinsics.cpp at link
+// time, building a trampoline needs compile-time IR access to the
+// *specific* LLVM Function backing a closure literal (to mark its `nest`
+// parameter) and to emit the alloca/init.trampoline/adjust.trampoline
+// sequence directly into the calling function's own block -- there's no
+// hook anywhere in this compiler for a rust-intrinsic call to do
+// caller-side IR surgery like that, and no coercion site (e.g. at the
+// point a `fn@`/`fn~`/`fn&` literal is passed where a bare `fn` argument
+// is expected) that calls this yet. This is prerequisite plumbing for
+// that future coercion, not a usable closure-to-bare-fn-pointer bridge
+// today.
+fn trans_closure_trampoline(bcx: block, llfn: ValueRef, llenvptr: ValueRef,
+                            llcallee_ty: TypeRef) -> ValueRef unsafe {
+    let ccx = bcx.ccx();
+    llvm::LLVMAddAttribute(llvm::LLVMGetParam(llfn, 1u as c_uint),
+                           lib::llvm::NestAttribute as c_uint);
+    let tramp = ArrayAlloca(bcx, T_i8(), C_uint(ccx, trampoline_buf_sz));
+    llvm::LLVMSetAlignment(tramp, trampoline_buf_align as c_uint);
+    let callee_i8 = PointerCast(bcx, llfn, T_ptr(T_i8()));
+    let env_i8 = PointerCast(bcx, llenvptr, T_ptr(T_i8()));
+    Call(bcx, ccx.intrinsics.get("llvm.init.trampoline"),
+        [tramp, callee_i8, env_i8]);
+    let adj = Call(bcx, ccx.intrinsics.get("llvm.adjust.trampoline"),
+                   [tramp]);
+    ret PointerCast(bcx, adj, llcallee_ty);
+}
+
 fn declare_dbg_intrinsics(llmod: ModuleRef,
                           intrinsics: hashmap<str, ValueRef>) {
     let declare =
@@ -4592,6 +6407,19 @@ fn trap(bcx: block) {
     }
 }
 
+fn call_lifetime_intrinsic(cx: block, name: str, llptr: ValueRef,
+                           llty: TypeRef) {
+    let ccx = cx.ccx();
+    alt ccx.intrinsics.find(name) {
+      some(x) {
+        let size = C_i64(llsize_of_real(ccx, llty) as i64);
+        let ptr = PointerCast(cx, llptr, T_ptr(T_i8()));
+        Call(cx, x, [size, ptr]);
+      }
+      _ { cx.sess().bug("unbound " + name + " in call_lifetime_intrinsic"); }
+    }
+}
+
 fn create_module_map(ccx: crate_ctxt) -> ValueRef {
     let elttype = T_struct([ccx.int_type, ccx.int_type]);
     let maptype = T_array(elttype, ccx.module_data.size() + 1u);
@@ -4700,7 +6528,10 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
         llvm::LLVMModuleCreateWithNameInContext
             (buf, llvm::LLVMGetGlobalContext())
     });
-    let data_layout = sess.targ_cfg.target_strs.data_layout;
+    let data_layout = alt sess.opts.target_data_layout {
+      some(dl) { dl }
+      none { sess.targ_cfg.target_strs.data_layout }
+    };
     let targ_triple = sess.targ_cfg.target_strs.target_triple;
     let _: () =
         str::as_buf(data_layout,
@@ -4709,10 +6540,10 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
         str::as_buf(targ_triple,
                     {|buf| llvm::LLVMSetTarget(llmod, buf) });
     let targ_cfg = sess.targ_cfg;
-    let td = mk_target_data(sess.targ_cfg.target_strs.data_layout);
+    let td = mk_target_data(data_layout);
     let tn = mk_type_names();
     let intrinsics = declare_intrinsics(llmod);
-    if sess.opts.extra_debuginfo {
+    if sess.opts.debuginfo >= 2u {
         declare_dbg_intrinsics(llmod, intrinsics);
     }
     let int_type = T_int(targ_cfg);
@@ -4723,7 +6554,7 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
     let tydesc_type = T_tydesc(targ_cfg);
     lib::llvm::associate_type(tn, "tydesc", tydesc_type);
     let crate_map = decl_crate_map(sess, link_meta.name, llmod);
-    let dbg_cx = if sess.opts.debuginfo {
+    let dbg_cx = if sess.opts.debuginfo >= 1u {
         option::some(@{llmetadata: map::new_int_hash(),
                        names: new_namegen()})
     } else {
@@ -4739,14 +6570,22 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
           item_ids: new_int_hash::<ValueRef>(),
           ast_map: amap,
           exp_map: emap,
+          reachable_items: compute_reachable_items(crate),
           item_symbols: new_int_hash::<str>(),
+          no_mangle_symbols: new_str_hash::<span>(),
+          item_ccs: new_int_hash::<lib::llvm::CallConv>(),
           mutable main_fn: none::<ValueRef>,
+          mutable pending_unpredictable: false,
           link_meta: link_meta,
           enum_sizes: ty::new_ty_hash(),
           discrims: ast_util::new_def_id_hash::<ValueRef>(),
           discrim_symbols: new_int_hash::<str>(),
           consts: new_int_hash::<ValueRef>(),
           tydescs: ty::new_ty_hash(),
+          shape_take_glues: new_str_hash::<ValueRef>(),
+          shape_drop_glues: new_str_hash::<ValueRef>(),
+          shape_free_glues: new_str_hash::<ValueRef>(),
+          cmp_glue: none,
           dicts: map::mk_hashmap(hash_dict_id, {|a, b| a == b}),
           monomorphized: map::mk_hashmap(hash_mono_id, {|a, b| a == b}),
           module_data: new_str_hash::<ValueRef>(),
@@ -4768,10 +6607,14 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
                mutable n_glues_created: 0u,
                mutable n_null_glues: 0u,
                mutable n_real_glues: 0u,
+               mutable n_glues_deduped: 0u,
+               mutable n_tydescs_deduped: 0u,
+               mutable n_shape_table_bytes: 0u,
+               mutable n_glues_elided: 0u,
                fn_times: @mutable []},
           upcalls:
               upcall::declare_upcalls(targ_cfg, tn, tydesc_type,
-                                      llmod),
+                                      llmod, sess.opts.personality),
           tydesc_type: tydesc_type,
           int_type: int_type,
           float_type: float_type,
@@ -4799,6 +6642,10 @@ fn trans_crate(sess: session::session, crate: @ast::crate, tcx: ty::ctxt,
         #error("n_glues_created: %u", ccx.stats.n_glues_created);
         #error("n_null_glues: %u", ccx.stats.n_null_glues);
         #error("n_real_glues: %u", ccx.stats.n_real_glues);
+        #error("n_glues_deduped: %u", ccx.stats.n_glues_deduped);
+        #error("n_tydescs_deduped: %u", ccx.stats.n_tydescs_deduped);
+        #error("n_shape_table_bytes: %u", ccx.stats.n_shape_table_bytes);
+        #error("n_glues_elided: %u", ccx.stats.n_glues_elided);
 
         for timing: {ident: str, time: int} in *ccx.stats.fn_times {
             #error("time: %s took %d ms", timing.ident, timing.time);
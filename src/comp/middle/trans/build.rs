@@ -58,7 +58,50 @@ fn CondBr(cx: block, If: ValueRef, Then: BasicBlockRef,
     if cx.unreachable { ret; }
     assert (!cx.terminated);
     cx.terminated = true;
-    llvm::LLVMBuildCondBr(B(cx), If, Then, Else);
+    let br = llvm::LLVMBuildCondBr(B(cx), If, Then, Else);
+    if cx.ccx().pending_unpredictable {
+        cx.ccx().pending_unpredictable = false;
+        SetUnpredictable(br);
+    }
+}
+
+// Tags a branch instruction with `!unpredictable` metadata so LLVM prefers
+// a branchless (cmov-style) lowering over a real conditional branch.
+fn SetUnpredictable(Br: ValueRef) unsafe {
+    let kind_id = str::as_buf("unpredictable", {|buf|
+        llvm::LLVMGetMDKindID(buf, str::len_bytes("unpredictable") as c_uint)
+    });
+    let node = llvm::LLVMMDNode(ptr::null::<ValueRef>(), 0u as c_uint);
+    llvm::LLVMSetMetadata(Br, kind_id, node);
+}
+
+// Tags a load with `!align` metadata, telling the optimizer the loaded
+// pointer is at least `align` bytes aligned so a later dereference doesn't
+// need to re-derive or re-check alignment on its own.
+fn SetAlignMetadata(Load: ValueRef, align: uint) unsafe {
+    let kind_id = str::as_buf("align", {|buf|
+        llvm::LLVMGetMDKindID(buf, str::len_bytes("align") as c_uint)
+    });
+    let node = llvm::LLVMMDNode(
+        vec::unsafe::to_ptr([C_uint_metadata(align)]), 1u as c_uint);
+    llvm::LLVMSetMetadata(Load, kind_id, node);
+}
+
+// Tags a load with `!dereferenceable` metadata, telling the optimizer that
+// the loaded pointer is safe to load `size` bytes through without a null or
+// bounds check, since the box/unique allocation it points into is at least
+// that big.
+fn SetDereferenceableMetadata(Load: ValueRef, size: uint) unsafe {
+    let kind_id = str::as_buf("dereferenceable", {|buf|
+        llvm::LLVMGetMDKindID(buf, str::len_bytes("dereferenceable") as c_uint)
+    });
+    let node = llvm::LLVMMDNode(
+        vec::unsafe::to_ptr([C_uint_metadata(size)]), 1u as c_uint);
+    llvm::LLVMSetMetadata(Load, kind_id, node);
+}
+
+fn C_uint_metadata(i: uint) -> ValueRef {
+    ret llvm::LLVMConstInt(T_i64(), i as u64, False);
 }
 
 fn Switch(cx: block, V: ValueRef, Else: BasicBlockRef, NumCases: uint)
@@ -117,6 +160,20 @@ fn FastInvoke(cx: block, Fn: ValueRef, Args: [ValueRef],
     }
 }
 
+fn InvokeWithConv(cx: block, Fn: ValueRef, Args: [ValueRef],
+                  Then: BasicBlockRef, Catch: BasicBlockRef,
+                  Conv: lib::llvm::CallConv) {
+    if cx.unreachable { ret; }
+    assert (!cx.terminated);
+    cx.terminated = true;
+    unsafe {
+        let v = llvm::LLVMBuildInvoke(B(cx), Fn, vec::to_ptr(Args),
+                                      Args.len() as c_uint,
+                                      Then, Catch, noname());
+        lib::llvm::SetInstructionCallConv(v, Conv);
+    }
+}
+
 fn Unreachable(cx: block) {
     if cx.unreachable { ret; }
     cx.unreachable = true;
@@ -656,6 +713,13 @@ fn SetCleanup(_cx: block, LandingPad: ValueRef) {
     llvm::LLVMSetCleanup(LandingPad, lib::llvm::True);
 }
 
+// A null i8* clause value means "catch anything", which is all trans_try
+// needs since fail values in this ABI carry no user-visible payload type
+// to discriminate on.
+fn AddClause(_cx: block, LandingPad: ValueRef, ClauseVal: ValueRef) {
+    llvm::LLVMAddClause(LandingPad, ClauseVal);
+}
+
 fn Resume(cx: block, Exn: ValueRef) -> ValueRef {
     assert (!cx.terminated);
     cx.terminated = true;
@@ -211,6 +211,21 @@ fn allocate_cbox(bcx: block,
         let cbox_ty = tuplify_box_ty(tcx, cdata_ty);
         let {bcx, val: box} = base::alloc_ty(bcx, cbox_ty);
         nuke_ref_count(bcx, box);
+        // A ck_block environment is just an ordinary stack alloca (unlike
+        // ck_box/ck_uniq, which heap-allocate), so it can be given the
+        // same llvm.lifetime.start/end bracketing base::alloc_local gives
+        // an ordinary local: its lifetime is the enclosing scope, ending
+        // (and letting LLVM reuse the stack slot) when that scope's other
+        // cleanups run, not "when the closure is dropped" the way a
+        // ck_box/ck_uniq environment's would.
+        if type_has_static_size(ccx, cbox_ty) {
+            let llty = type_of(ccx, cbox_ty);
+            base::call_lifetime_intrinsic(bcx, "llvm.lifetime.start", box,
+                                          llty);
+            in_scope_cx(bcx) {|info|
+                info.lifetime_ends += [(box, llty)];
+            }
+        }
         (bcx, box)
       }
     };
@@ -328,7 +343,10 @@ fn store_environment(
           }
           env_move(val, ty, kind) {
             let src = {bcx:bcx, val:val, kind:kind};
-            bcx = move_val(bcx, INIT, bound_data, src, ty);
+            // No expr id is available here to consult last_uses against,
+            // so always zero -- the upvar's home slot may still be read
+            // or dropped by the enclosing scope.
+            bcx = move_val(bcx, INIT, bound_data, src, ty, false);
           }
           env_ref(val, ty, owned) {
             Store(bcx, val, bound_data);
@@ -455,14 +473,15 @@ fn trans_expr_fn(bcx: block,
     let sub_path = bcx.fcx.path + [path_name("anon")];
     let s = mangle_internal_name_by_path(ccx, sub_path);
     let llfn = decl_internal_cdecl_fn(ccx.llmod, s, llfnty);
-    register_fn(ccx, sp, sub_path, "anon fn", [], id);
+    register_fn(ccx, sp, sub_path, "anon fn", [], id, []);
 
     let trans_closure_env = fn@(ck: ty::closure_kind) -> ValueRef {
         let cap_vars = capture::compute_capture_vars(
             ccx.tcx, id, proto, cap_clause);
         let {llbox, cdata_ty, bcx} = build_closure(bcx, cap_vars, ck);
         trans_closure(ccx, sub_path, decl, body, llfn, no_self, [],
-                      bcx.fcx.param_substs, id, {|fcx|
+                      bcx.fcx.param_substs, id, maybe_trans_instrument_enter,
+                      {|fcx|
             load_environment(bcx, fcx, cdata_ty, cap_vars, ck);
         });
         llbox
@@ -474,7 +493,7 @@ fn trans_expr_fn(bcx: block,
       ast::proto_uniq { trans_closure_env(ty::ck_uniq) }
       ast::proto_bare {
         trans_closure(ccx, sub_path, decl, body, llfn, no_self, [], none,
-                      id, {|_fcx|});
+                      id, maybe_trans_instrument_enter, {|_fcx|});
         C_null(T_opaque_box_ptr(ccx))
       }
     };
@@ -631,7 +650,7 @@ fn make_opaque_cbox_take_glue(
         let malloc = ccx.upcalls.shared_malloc;
         let cbox_out = Call(bcx, malloc, [sz]);
         let cbox_out = PointerCast(bcx, cbox_out, llopaquecboxty);
-        let {bcx, val: _} = call_memmove(bcx, cbox_out, cbox_in, sz);
+        let {bcx, val: _} = call_memmove(bcx, cbox_out, cbox_in, sz, 1u);
         Store(bcx, cbox_out, cboxptr);
 
         // Take the (deeply cloned) type descriptor
@@ -642,8 +661,7 @@ fn make_opaque_cbox_take_glue(
         let ti = none;
         let cdata_out = GEPi(bcx, cbox_out, [0, abi::box_field_body]);
         call_tydesc_glue_full(bcx, cdata_out, tydesc,
-                              abi::tydesc_field_take_glue, ti);
-        bcx
+                              abi::tydesc_field_take_glue, ti)
     }
 }
 
@@ -687,8 +705,8 @@ fn make_opaque_cbox_free_glue(
         // Drop the tuple data then free the descriptor
         let ti = none;
         let cdata = GEPi(bcx, cbox, [0, abi::box_field_body]);
-        call_tydesc_glue_full(bcx, cdata, tydesc,
-                              abi::tydesc_field_drop_glue, ti);
+        let bcx = call_tydesc_glue_full(bcx, cdata, tydesc,
+                                        abi::tydesc_field_drop_glue, ti);
 
         // Free the ty descr (if necc) and the box itself
         alt ck {
@@ -17,6 +17,7 @@ import lib::llvm::{llvm, target_data, type_names, associate_type,
                    name_has_type};
 import lib::llvm::{ModuleRef, ValueRef, TypeRef, BasicBlockRef, BuilderRef};
 import lib::llvm::{True, False, Bool};
+import lib::llvm::CallConv;
 import metadata::csearch;
 import ast_map::path;
 
@@ -28,14 +29,36 @@ fn new_namegen() -> namegen {
 
 type derived_tydesc_info = {lltydesc: ValueRef, escapes: bool};
 
+// Cached result of a dynamic size_of/align_of computation for a type,
+// so that GEP_tup_like's compute_off doesn't re-emit the tydesc-driven IR
+// for the same type every time it's field-indexed into.
+type size_align_metrics = {sz: ValueRef, align: ValueRef};
+
 type tydesc_info =
     {ty: ty::t,
      tydesc: ValueRef,
      size: ValueRef,
      align: ValueRef,
+     // Preferred, as opposed to align's ABI-mandated, alignment -- see
+     // trans::shape::llalign_of_pref. Only ever differs from `align` for a
+     // handful of scalar types on a handful of targets (e.g. f64 on x86),
+     // so most tydescs have align == pref_align.
+     pref_align: ValueRef,
+     // Set once, in declare_tydesc, from ty::type_needs_drop(ty). When
+     // false, take/drop/free are all no-ops for this type (see
+     // make_take_glue/make_drop_glue/make_free_glue's scalar fallthrough
+     // arms), so lazily_emit_tydesc_glue never bothers creating those glue
+     // functions and emit_tydescs fills the corresponding tydesc slots
+     // with null instead.
+     needs_drop: bool,
      mutable take_glue: option<ValueRef>,
      mutable drop_glue: option<ValueRef>,
      mutable free_glue: option<ValueRef>,
+     // Unlike take/drop/free, comparison glue is only ever wanted for
+     // types that are actually compared somewhere in the crate, so this
+     // stays none until call_cmp_glue lazily fills it in (see
+     // lazily_emit_cmp_glue).
+     mutable cmp_glue: option<ValueRef>,
      ty_params: [uint]};
 
 /*
@@ -59,6 +82,10 @@ type stats =
      mutable n_glues_created: uint,
      mutable n_null_glues: uint,
      mutable n_real_glues: uint,
+     mutable n_glues_deduped: uint,
+     mutable n_tydescs_deduped: uint,
+     mutable n_shape_table_bytes: uint,
+     mutable n_glues_elided: uint,
      fn_times: @mutable [{ident: str, time: int}]};
 
 resource BuilderRef_res(B: BuilderRef) { llvm::LLVMDisposeBuilder(B); }
@@ -74,14 +101,49 @@ type crate_ctxt = @{
      item_ids: hashmap<ast::node_id, ValueRef>,
      ast_map: ast_map::map,
      exp_map: resolve::exp_map,
+     // Every item transitively reachable from the crate root by a chain of
+     // `export`s, computed once up front by base::compute_reachable_items
+     // (the same top-down is_exported walk metadata::encoder and
+     // rustdoc::prune_unexported_pass each do independently for their own
+     // purposes). register_fn_fuller consults this to decide whether an
+     // ordinary fn can safely get internal linkage.
+     reachable_items: hashmap<ast::node_id, ()>,
      item_symbols: hashmap<ast::node_id, str>,
+     // Symbols claimed by a #[no_mangle] item, so a second item asking for
+     // the same verbatim name can be rejected instead of silently
+     // colliding at link time (there's no seq-suffix disambiguation for
+     // these the way there is for mangle_internal_name_by_seq).
+     no_mangle_symbols: hashmap<str, span>,
+     // The calling convention register_fn_fuller declared each local fn
+     // with (see attr::find_fn_call_conv), so a direct call site can pick
+     // the matching convention for its Call/Invoke instruction instead of
+     // assuming the default. Every fn register_fn_fuller declares in this
+     // crate, generic or not, is entered here; fns of other crates never
+     // are -- see lval_static_fn's use of it.
+     item_ccs: hashmap<ast::node_id, CallConv>,
      mutable main_fn: option<ValueRef>,
+     // Set just after translating a call to the `unpredictable`
+     // rust-intrinsic and cleared by the next CondBr it feeds, which
+     // tags that branch with `!unpredictable` metadata.
+     mutable pending_unpredictable: bool,
      link_meta: link::link_meta,
      enum_sizes: hashmap<ty::t, uint>,
      discrims: hashmap<ast::def_id, ValueRef>,
      discrim_symbols: hashmap<ast::node_id, str>,
      consts: hashmap<ast::node_id, ValueRef>,
      tydescs: hashmap<ty::t, @tydesc_info>,
+     // Take/drop/free glue is shape-driven, so structurally identical
+     // types (e.g. any two-pointer record) can share one glue function;
+     // these cache the glue already emitted for a given shape, keyed on
+     // the raw shape bytes (see shape::shape_of).
+     shape_take_glues: hashmap<str, ValueRef>,
+     shape_drop_glues: hashmap<str, ValueRef>,
+     shape_free_glues: hashmap<str, ValueRef>,
+     // Comparison glue is a single fixed trampoline into upcalls.cmp_type
+     // regardless of the type being compared (see make_cmp_glue), so unlike
+     // the take/drop/free caches above there's nothing to key on: this just
+     // holds the one instance, created the first time any type is compared.
+     mutable cmp_glue: option<ValueRef>,
      dicts: hashmap<dict_id, ValueRef>,
      monomorphized: hashmap<mono_id, {llfn: ValueRef, fty: ty::t}>,
      module_data: hashmap<str, ValueRef>,
@@ -186,6 +248,12 @@ type fn_ctxt = @{
     // table for the next time that such a tydesc is needed.
     derived_tydescs: hashmap<ty::t, derived_tydesc_info>,
 
+    // Same idea as derived_tydescs, but memoizes the size/align ValueRefs
+    // computed for dynamically-sized types by shape::size_of/align_of, so
+    // that repeated GEP_tup_like accesses into the same type don't re-emit
+    // the same tydesc-driven size/align computation.
+    size_align_metrics: hashmap<ty::t, size_align_metrics>,
+
     // The node_id of the function, or -1 if it doesn't correspond to
     // a user-defined function.
     id: ast::node_id,
@@ -211,7 +279,7 @@ fn warn_not_to_commit(ccx: crate_ctxt, msg: str) {
 }
 
 enum cleanup {
-    clean(fn@(block) -> block),
+    clean(ValueRef, fn@(block) -> block),
     clean_temp(ValueRef, fn@(block) -> block),
 }
 
@@ -228,7 +296,7 @@ fn scope_clean_changed(info: scope_info) {
 fn add_clean(cx: block, val: ValueRef, ty: ty::t) {
     if !ty::type_needs_drop(cx.tcx(), ty) { ret; }
     in_scope_cx(cx) {|info|
-        info.cleanups += [clean(bind base::drop_ty(_, val, ty))];
+        info.cleanups += [clean(val, bind base::drop_ty(_, val, ty))];
         scope_clean_changed(info);
     }
 }
@@ -263,26 +331,37 @@ fn add_clean_free(cx: block, ptr: ValueRef, shared: bool) {
     }
 }
 
-// Note that this only works for temporaries. We should, at some point, move
-// to a system where we can also cancel the cleanup on local variables, but
-// this will be more involved. For now, we simply zero out the local, and the
-// drop glue checks whether it is zero.
+// Cancels a cleanup previously registered with add_clean/add_clean_temp/etc.
+// Unlike in_scope_cx (which only ever looks at the innermost scope), this
+// walks up the block-parent chain: a local's clean is registered in the
+// scope where it was declared, but the move that lets us cancel it may
+// happen in a nested child scope, so we have to search outward from `cx`
+// until we find (and remove) the matching entry.
 fn revoke_clean(cx: block, val: ValueRef) {
-    in_scope_cx(cx) {|info|
-        let i = 0u;
-        for cu in info.cleanups {
-            alt cu {
-              clean_temp(v, _) if v == val {
-                info.cleanups =
-                    vec::slice(info.cleanups, 0u, i) +
-                    vec::slice(info.cleanups, i + 1u, info.cleanups.len());
-                scope_clean_changed(info);
-                ret;
-              }
-              _ {}
+    let cur = cx;
+    while true {
+        alt cur.kind {
+          block_scope(info) {
+            let i = 0u;
+            for cu in info.cleanups {
+                alt cu {
+                  clean(v, _) | clean_temp(v, _) if v == val {
+                    info.cleanups =
+                        vec::slice(info.cleanups, 0u, i) +
+                        vec::slice(info.cleanups, i + 1u,
+                                   info.cleanups.len());
+                    scope_clean_changed(info);
+                    ret;
+                  }
+                  _ {}
+                }
+                i += 1u;
             }
-            i += 1u;
+          }
+          _ {}
         }
+        if cur.parent == parent_none { ret; }
+        cur = block_parent(cur);
     }
 }
 
@@ -331,6 +410,10 @@ type scope_info = {
     mutable cleanup_paths: [cleanup_path],
     // Unwinding landing pad. Also cleared when cleanups change.
     mutable landing_pad: option<BasicBlockRef>,
+    // Allocas for this scope's locals, so trans_block_cleanups can tell
+    // LLVM their lifetimes end here (see llvm.lifetime.start/end in
+    // alloc_local/trans_block_cleanups).
+    mutable lifetime_ends: [(ValueRef, TypeRef)],
 };
 
 // Basic block context.  We create a block context for each basic block
@@ -581,6 +664,24 @@ fn T_glue_fn(cx: crate_ctxt) -> TypeRef {
     ret t;
 }
 
+// Comparison glue has a different signature from take/drop/free glue (it's
+// binary, and reports its result through an out-parameter rather than
+// mutating in place), so it can't be pulled out of the tydesc like
+// T_glue_fn does; this just mirrors upcalls::cmp_type's own signature,
+// which is what every cmp glue function forwards to.
+fn T_cmp_glue_fn(cx: crate_ctxt) -> TypeRef {
+    let s = "cmp_glue_fn";
+    alt name_has_type(cx.tn, s) { some(t) { ret t; } _ {} }
+    let t = T_fn([T_ptr(T_i1()),
+                  T_ptr(cx.tydesc_type),
+                  T_ptr(T_ptr(cx.tydesc_type)),
+                  T_ptr(T_i8()),
+                  T_ptr(T_i8()),
+                  T_i8()], T_void());
+    associate_type(cx.tn, s, t);
+    ret t;
+}
+
 fn T_tydesc(targ_cfg: @session::config) -> TypeRef {
     let tydesc = T_named_struct("tydesc");
     let tydescpp = T_ptr(T_ptr(tydesc));
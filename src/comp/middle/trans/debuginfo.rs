@@ -16,6 +16,7 @@ import driver::session::session;
 export create_local_var;
 export create_function;
 export create_arg;
+export create_enum_metadata;
 export update_source_pos;
 export debug_ctxt;
 
@@ -38,6 +39,8 @@ const StructureTypeTag: int = 19;
 const MemberTag: int = 13;
 const ArrayTypeTag: int = 1;
 const SubrangeTag: int = 33;
+const EnumerationTypeTag: int = 4;
+const EnumeratorTag: int = 40;
 
 const DW_ATE_boolean: int = 0x02;
 const DW_ATE_float: int = 0x04;
@@ -480,6 +483,54 @@ fn create_composite_type(type_tag: int, name: str, file: ValueRef, line: int,
     ret llmdnode(lldata);
 }
 
+fn create_enumerator(name: str, value: int) -> ValueRef {
+    ret llmdnode([lltag(EnumeratorTag), llstr(name), lli64(value)]);
+}
+
+// Describes an enum type for the debugger, from the variant info in
+// ty::enum_variants. A C-like (all-nullary) enum maps directly onto
+// DWARF's enumeration type, one enumerator per variant, so gdb can print
+// the variant name. A tagged enum (any variant carrying data) doesn't fit
+// that shape; until create_ty can walk a variant's payload types, it's
+// described as a struct exposing just the discriminant, which is at
+// least enough for gdb to print the variant number.
+fn create_enum_metadata(cx: crate_ctxt, t: ty::t, item: ast::item)
+    -> @metadata<tydesc_md> {
+    let cache = get_cache(cx);
+    let tg = EnumerationTypeTag;
+    alt cached_metadata::<@metadata<tydesc_md>>(
+        cache, tg, {|md| ty::type_id(t) == md.data.hash}) {
+      option::some(md) { ret md; }
+      option::none {}
+    }
+
+    let fname = filename_from_span(cx, item.span);
+    let file_node = create_file(cx, fname);
+    let loc = codemap::lookup_char_pos(cx.sess.codemap, item.span.lo);
+    let variants = ty::enum_variants(cx.tcx, ast_util::local_def(item.id));
+    let all_nullary = vec::all(*variants, {|v| v.args.len() == 0u});
+    let (size, align) = size_and_align_of::<int>();
+
+    let llnode = if all_nullary {
+        let enumerators = vec::map(*variants,
+            {|v| create_enumerator(v.name, v.disr_val)});
+        create_composite_type(EnumerationTypeTag, item.ident, file_node.node,
+                              loc.line as int, size * 8, align * 8, 0,
+                              option::none, option::some(enumerators))
+    } else {
+        let scx = create_structure(file_node, item.ident, loc.line as int);
+        let discr_ty = create_basic_type(cx, ty::mk_int(cx.tcx),
+                                         ast::ty_int(ast::ty_i), item.span);
+        add_member(scx, "discr", loc.line as int, size, align,
+                   discr_ty.node);
+        finish_structure(scx)
+    };
+    let mdval = @{node: llnode, data: {hash: ty::type_id(t)}};
+    update_cache(cache, tg, tydesc_metadata(mdval));
+    add_named_metadata(cx, "llvm.dbg.ty", llnode);
+    ret mdval;
+}
+
 fn create_vec(cx: crate_ctxt, vec_t: ty::t, elem_t: ty::t,
               vec_ty_span: codemap::span, elem_ty: @ast::ty)
     -> @metadata<tydesc_md> {
@@ -706,23 +757,27 @@ fn create_local_var(bcx: block, local: @ast::local)
     let mdval = @{node: mdnode, data: {id: local.node.id}};
     update_cache(cache, AutoVariableTag, local_var_metadata(mdval));
 
-    let llptr = alt bcx.fcx.lllocals.find(local.node.id) {
-      option::some(local_mem(v)) { v }
-      option::some(_) {
-        bcx.tcx().sess.span_bug(local.span, "local is bound to \
-                something weird");
+    let llself = alt bcx.fcx.lllocals.find(local.node.id) {
+      option::some(v) { v }
+      option::none { bcx.fcx.lllocals.get(local.node.pat.id) }
+    };
+    alt llself {
+      local_mem(v) {
+        // The local has a stack slot: describe its address with
+        // dbg.declare, as usual.
+        let declargs = [llmdnode([v]), mdnode];
+        trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.declare"),
+                           declargs);
       }
-      option::none {
-        alt bcx.fcx.lllocals.get(local.node.pat.id) {
-          local_imm(v) { v }
-          _ { bcx.tcx().sess.span_bug(local.span, "local is bound to \
-                something weird"); }
-        }
+      local_imm(v) {
+        // The local was kept as an SSA value under optimization and has
+        // no address; dbg.declare would have nothing to point at, so
+        // describe its value directly with dbg.value instead.
+        let valargs = [llmdnode([v]), C_i64(0), mdnode];
+        trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.value"),
+                           valargs);
       }
     };
-    let declargs = [llmdnode([llptr]), mdnode];
-    trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.declare"),
-                       declargs);
     ret mdval;
 }
 
@@ -748,17 +803,27 @@ fn create_arg(bcx: block, arg: ast::arg, sp: span)
     let mdval = @{node: mdnode, data: {id: arg.id}};
     update_cache(cache, tg, argument_metadata(mdval));
 
-    let llptr = alt fcx.llargs.get(arg.id) {
-      local_mem(v) | local_imm(v) { v }
+    alt fcx.llargs.get(arg.id) {
+      local_mem(v) {
+        let declargs = [llmdnode([v]), mdnode];
+        trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.declare"),
+                           declargs);
+      }
+      local_imm(v) {
+        // No stack slot for this arg under optimization; describe its
+        // value directly so debuggers don't lose it.
+        let valargs = [llmdnode([v]), C_i64(0), mdnode];
+        trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.value"),
+                           valargs);
+      }
     };
-    let declargs = [llmdnode([llptr]), mdnode];
-    trans::build::Call(bcx, cx.intrinsics.get("llvm.dbg.declare"),
-                       declargs);
     ret mdval;
 }
 
 fn update_source_pos(cx: block, s: span) {
-    if !cx.sess().opts.debuginfo {
+    // Line tables (level 1) are enough to drive this; full variable/type
+    // metadata (level 2) isn't needed for accurate line numbers.
+    if cx.sess().opts.debuginfo < 1u {
         ret;
     }
     let cm = cx.sess().codemap;
@@ -837,7 +902,7 @@ fn create_function(fcx: fn_ctxt) -> @metadata<subprogram_md> {
     let file_node = create_file(cx, loc.file.name).node;
     let key = if cx.item_symbols.contains_key(fcx.id) { fcx.id } else { id };
     let mangled = cx.item_symbols.get(key);
-    let ty_node = if cx.sess.opts.extra_debuginfo {
+    let ty_node = if cx.sess.opts.debuginfo >= 2u {
         alt ret_ty.node {
           ast::ty_nil { llnull() }
           _ { create_ty(cx, ty::node_id_to_type(cx.tcx, id), ret_ty).node }
@@ -116,9 +116,8 @@ fn trans_vtable_callee(bcx: block, env: callee_env, dict: ValueRef,
     let {ty: fty, llty: llfty} =
         wrapper_fn_ty(ccx, val_ty(dict), node_id_type(bcx, callee_id),
                       method.tps);
-    let vtable = PointerCast(bcx, Load(bcx, GEPi(bcx, dict, [0, 0])),
-                             T_ptr(T_array(T_ptr(llfty), n_method + 1u)));
-    let mptr = Load(bcx, GEPi(bcx, vtable, [0, n_method as int]));
+    let vtable = Load(bcx, GEPi(bcx, dict, [0, 0]));
+    let mptr = trans_vtable_entry(bcx, vtable, n_method, llfty);
     let generic = generic_none;
     if (*method.tps).len() > 0u || ty::type_has_params(fty) {
         let tydescs = [], tis = [];
@@ -206,16 +205,58 @@ fn llfn_arg_tys(ft: TypeRef) -> {inputs: [TypeRef], output: TypeRef} {
 
 fn trans_vtable(ccx: crate_ctxt, id: ast::node_id, name: str,
                 ptrs: [ValueRef]) {
-    let tbl = C_struct(ptrs);
-    let vt_gvar = str::as_buf(name, {|buf|
-        llvm::LLVMAddGlobal(ccx.llmod, val_ty(tbl), buf)
-    });
-    llvm::LLVMSetInitializer(vt_gvar, tbl);
+    let vt_gvar = if ccx.sess.opts.relative_vtables {
+        // Forward-declare the global with its final ([n x i32]) type so
+        // its own address is available, unlike the absolute-pointer
+        // struct type below, without first knowing its initializer.
+        let n = ptrs.len();
+        let gvar = str::as_buf(name, {|buf|
+            llvm::LLVMAddGlobal(ccx.llmod, T_array(T_i32(), n), buf)
+        });
+        let vt_addr = llvm::LLVMConstPtrToInt(gvar, ccx.int_type);
+        let offs = vec::map(ptrs, {|p|
+            let fn_addr = llvm::LLVMConstPtrToInt(p, ccx.int_type);
+            llvm::LLVMConstTrunc(llvm::LLVMConstSub(fn_addr, vt_addr),
+                                 T_i32())
+        });
+        llvm::LLVMSetInitializer(gvar, C_array(T_i32(), offs));
+        gvar
+    } else {
+        let tbl = C_struct(ptrs);
+        let gvar = str::as_buf(name, {|buf|
+            llvm::LLVMAddGlobal(ccx.llmod, val_ty(tbl), buf)
+        });
+        llvm::LLVMSetInitializer(gvar, tbl);
+        gvar
+    };
     llvm::LLVMSetGlobalConstant(vt_gvar, lib::llvm::True);
     ccx.item_ids.insert(id, vt_gvar);
     ccx.item_symbols.insert(id, name);
 }
 
+// Reads method `n`'s entry out of `vtable` (the dict's raw vtable
+// pointer, i.e. what's loaded from GEPi(dict, [0, 0]) at each call
+// site below) and returns a callable pointer of type `llfty`. Under
+// --relative-vtables (see trans_vtable above), `vtable` points to an
+// array of 32-bit offsets rather than function pointers, so the entry
+// is added back to the vtable's own address and int-to-ptr cast instead
+// of loaded directly.
+fn trans_vtable_entry(bcx: block, vtable: ValueRef, n: uint,
+                      llfty: TypeRef) -> ValueRef {
+    let ccx = bcx.ccx();
+    if ccx.sess.opts.relative_vtables {
+        let vt = PointerCast(bcx, vtable, T_ptr(T_array(T_i32(), n + 1u)));
+        let off = Load(bcx, GEPi(bcx, vt, [0, n as int]));
+        let vt_addr = PtrToInt(bcx, vt, ccx.int_type);
+        // off is a signed offset (the target fn may land either side of
+        // the vtable in the module), so this must sign- not zero-extend.
+        let fn_addr = Add(bcx, vt_addr, SExt(bcx, off, ccx.int_type));
+        ret IntToPtr(bcx, fn_addr, T_ptr(llfty));
+    }
+    let vt = PointerCast(bcx, vtable, T_ptr(T_array(T_ptr(llfty), n + 1u)));
+    ret Load(bcx, GEPi(bcx, vt, [0, n as int]));
+}
+
 fn find_dict_in_fn_ctxt(ps: param_substs, n_param: uint, n_bound: uint)
     -> typeck::dict_origin {
     let dict_off = n_bound, i = 0u;
@@ -358,9 +399,8 @@ fn trans_iface_wrapper(ccx: crate_ctxt, pt: path, m: ty::method,
         let dict = Load(bcx, GEPi(bcx, param, [0, 0]));
         let box = Load(bcx, GEPi(bcx, param, [0, 1]));
         let self = GEPi(bcx, box, [0, abi::box_field_body]);
-        let vtable = PointerCast(bcx, Load(bcx, GEPi(bcx, dict, [0, 0])),
-                                 T_ptr(T_array(T_ptr(llfty), n + 1u)));
-        let mptr = Load(bcx, GEPi(bcx, vtable, [0, n as int]));
+        let vtable = Load(bcx, GEPi(bcx, dict, [0, 0]));
+        let mptr = trans_vtable_entry(bcx, vtable, n, llfty);
         let args = [PointerCast(bcx, dict, T_ptr(T_i8())),
                     LLVMGetParam(llfn, 1u as c_uint),
                     PointerCast(bcx, self, T_opaque_cbox_ptr(ccx))];
@@ -196,6 +196,20 @@ fn trans_native_mod(ccx: crate_ctxt,
         // Declare the "prototype" for the base function F:
         let llbasefnty = T_fn(tys.arg_tys, tys.ret_ty);
         let llbasefn = decl_fn(ccx.llmod, lname, cc, llbasefnty);
+        if attr::attrs_contains_name(native_item.attrs, "returns_twice") {
+            // setjmp-like functions return more than once; tell LLVM so
+            // it doesn't make invalid assumptions across the call.
+            set_returns_twice(llbasefn);
+        }
+        // A stray unwind out of an extern "C" function (e.g. a C++
+        // exception) crossing back into Rust is UB, so by default mark
+        // the callee `nounwind`: any unwind attempting to cross it hits
+        // undefined behaviour rather than propagating into Rust frames
+        // that never installed a landing pad for it. `#[unwind]` opts a
+        // specific native function back into being called as unwindable.
+        if !attr::attrs_contains_name(native_item.attrs, "unwind") {
+            set_nounwind(llbasefn);
+        }
         // Name the shim function
         let shim_name = lname + "__c_stack_shim";
         ret build_shim_fn_(ccx, shim_name, llbasefn, tys, cc,
@@ -349,11 +363,11 @@ fn trans_crust_fn(ccx: crate_ctxt, path: ast_map::path, decl: ast::fn_decl,
     build_wrap_fn(ccx, llshimfn, llwrapfn, tys)
 }
 
-fn register_crust_fn(ccx: crate_ctxt, sp: span,
-                     path: ast_map::path, node_id: ast::node_id) {
+fn register_crust_fn(ccx: crate_ctxt, sp: span, path: ast_map::path,
+                     node_id: ast::node_id, attrs: [ast::attribute]) {
     let t = ty::node_id_to_type(ccx.tcx, node_id);
     let (llargtys, llretty, _) = c_arg_and_ret_lltys(ccx, node_id);
     let llfty = T_fn(llargtys, llretty);
     register_fn_fuller(ccx, sp, path, "crust fn", node_id,
-                       t, lib::llvm::CCallConv, llfty);
+                       t, lib::llvm::CCallConv, llfty, attrs);
 }
\ No newline at end of file
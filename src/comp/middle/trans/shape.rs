@@ -269,7 +269,7 @@ fn s_send_tydesc(_tcx: ty_ctxt) -> u8 {
 
 fn mk_ctxt(llmod: ModuleRef) -> ctxt {
     let llshapetablesty = trans::common::T_named_struct("shapes");
-    let llshapetables = str::as_buf("shapes", {|buf|
+    let llshapetables = str::as_buf("_rust_shape_tables", {|buf|
         lib::llvm::llvm::LLVMAddGlobal(llmod, llshapetablesty, buf)
     });
 
@@ -569,6 +569,14 @@ fn gen_shape_tables(ccx: crate_ctxt) {
     lib::llvm::llvm::LLVMSetGlobalConstant(ccx.shape_cx.llshapetables, True);
     lib::llvm::SetLinkage(ccx.shape_cx.llshapetables,
                           lib::llvm::InternalLinkage);
+    // Give the blob a stable, greppable symbol (set above, in mk_ctxt) and
+    // its own section, so it can be located and stripped independently of
+    // the rest of .rodata when inspecting or trimming the reflection data.
+    str::as_buf(ccx.sess.targ_cfg.target_strs.shape_sect_name, {|buf|
+        llvm::LLVMSetSection(ccx.shape_cx.llshapetables, buf)
+    });
+    ccx.stats.n_shape_table_bytes +=
+        llsize_of_real(ccx, ccx.shape_cx.llshapetablesty);
 }
 
 // ______________________________________________________________________
@@ -607,6 +615,25 @@ fn align_of(bcx: block, t: ty::t) -> result {
     }
 }
 
+// Like align_of, but the preferred (rather than ABI-mandated) alignment --
+// see llalign_of_pref for what that means and why it can differ.
+fn pref_align_of(bcx: block, t: ty::t) -> result {
+    let ccx = bcx.ccx();
+    if check type_has_static_size(ccx, t) {
+        rslt(bcx, llalign_of_pref(ccx, type_of::type_of(ccx, t)))
+    } else {
+        // dynamic_metrics's shape-interpreting visitor (align_elements,
+        // compute_static_enum_size) only ever computes ABI alignment --
+        // that's what's needed for real struct layout -- so there's no
+        // preferred-alignment equivalent of it to call here. A type whose
+        // size isn't known until runtime falls back to its ABI alignment,
+        // which is always a valid (if not always minimal) alignment to
+        // report as "preferred", rather than duplicating that whole
+        // visitor for what's otherwise a debugging/tuning-oriented value.
+        align_of(bcx, t)
+    }
+}
+
 fn metrics(bcx: block, t: ty::t) -> metrics {
     let ccx = bcx.ccx();
     if check type_has_static_size(ccx, t) {
@@ -622,7 +649,11 @@ fn llsize_of_real(cx: crate_ctxt, t: TypeRef) -> uint {
     ret llvm::LLVMStoreSizeOfType(cx.td.lltd, t) as uint;
 }
 
-// Returns the real alignment of the given type for the current target.
+// Returns the real (i.e. preferred, not ABI-mandated -- see llalign_of
+// below) alignment of the given type for the current target. Used where
+// alignment is a hint rather than a correctness requirement, e.g. the
+// memcpy/memset/alloca alignment arguments, since a backend is always free
+// to do at least as well with the preferred alignment as with the ABI one.
 fn llalign_of_real(cx: crate_ctxt, t: TypeRef) -> uint {
     ret llvm::LLVMPreferredAlignmentOfType(cx.td.lltd, t) as uint;
 }
@@ -632,11 +663,24 @@ fn llsize_of(cx: crate_ctxt, t: TypeRef) -> ValueRef {
                                False);
 }
 
+// Returns the ABI-mandated alignment of the given type, i.e. the alignment
+// actually used to place it as a struct member or array element -- what
+// `align_of`/tydesc.align report. LLVMAlignOf's constant-fold is target-
+// data-independent and ABI, not preferred (they coincide for most types;
+// f64 on x86 is the standard example where they don't -- 4-byte ABI
+// alignment despite an 8-byte preferred one).
 fn llalign_of(cx: crate_ctxt, t: TypeRef) -> ValueRef {
     ret llvm::LLVMConstIntCast(lib::llvm::llvm::LLVMAlignOf(t), cx.int_type,
                                False);
 }
 
+// Returns the preferred alignment of the given type as an LLVM constant,
+// suitable for the tydesc pref_align field -- the pref_align_of/
+// llalign_of counterpart of llalign_of_real being to llalign_of.
+fn llalign_of_pref(cx: crate_ctxt, t: TypeRef) -> ValueRef {
+    ret C_uint(cx, llalign_of_real(cx, t));
+}
+
 // Computes the static size of a enum, without using mk_tup(), which is
 // bad for performance.
 //
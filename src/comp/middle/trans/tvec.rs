@@ -72,7 +72,7 @@ fn duplicate(bcx: block, vptr: ValueRef, vec_ty: ty::t) -> result {
     let size = Add(bcx, fill, llsize_of(ccx, ccx.opaque_vec_type));
     let {bcx: bcx, val: newptr} =
         trans_shared_malloc(bcx, val_ty(vptr), size);
-    let bcx = call_memmove(bcx, newptr, vptr, size).bcx;
+    let bcx = call_memmove(bcx, newptr, vptr, size, 1u).bcx;
     let unit_ty = ty::sequence_element_type(bcx.tcx(), vec_ty);
     Store(bcx, fill, GEPi(bcx, newptr, [0, abi::vec_elt_alloc]));
     if ty::type_needs_drop(bcx.tcx(), unit_ty) {
@@ -133,7 +133,7 @@ fn trans_str(bcx: block, s: str, dest: dest) -> block {
     let ccx = bcx.ccx();
     let llcstr = C_cstr(ccx, s);
     let bcx = call_memmove(bcx, get_dataptr(bcx, sptr, T_i8()), llcstr,
-                           C_uint(ccx, veclen)).bcx;
+                           C_uint(ccx, veclen), 1u).bcx;
     ret base::store_in_dest(bcx, sptr, dest);
 }
 
@@ -75,6 +75,21 @@ fn type_of(cx: crate_ctxt, t: ty::t) -> TypeRef {
       ty::ty_ptr(mt) {
         let mt_ty = mt.ty;
         T_ptr(type_of(cx, mt_ty)) }
+      // A `#[repr(simd)]` record of N homogeneous scalar fields lowering
+      // to an LLVM vector (LLVMVectorType, already bound in lib::llvm,
+      // along with LLVMBuildExtractElement/LLVMBuildInsertElement for
+      // element access) would belong here, alongside the plain T_struct
+      // case below. It can't be done yet: `#[repr(...)]` is parsed off a
+      // `type` alias item's attrs (see attr::find_repr_attr, which no
+      // trans code calls; the `#[repr(u8)]`-style enum case noted in
+      // trans::base near lookup_discriminant has the same gap), but a `type` alias is
+      // transparent by the time typeck produces a ty::t -- this ty_rec
+      // arm only ever sees the plain structural record, with no def_id
+      // to look its original item's attrs up by, unlike ty_enum/ty_res/
+      // ty_class which do carry one. Giving repr-attributed record
+      // aliases a nominal ty::sty of their own (or otherwise threading
+      // their attrs through to here) is a middle::ty change, not a trans
+      // one, so it's out of scope for this file.
       ty::ty_rec(fields) {
         let tys: [TypeRef] = [];
         for f: ty::field in fields {
@@ -103,12 +118,45 @@ fn type_of(cx: crate_ctxt, t: ty::t) -> TypeRef {
       ty::ty_opaque_closure_ptr(_) { T_opaque_box_ptr(cx) }
       ty::ty_constr(subt,_) { type_of(cx, subt) }
 
+      // A `union`-style overlapping-storage type isn't representable here
+      // yet: there's no ast::item_kind/parser production for it and no
+      // ty::sty variant (see the note by ty_tup in middle/ty.rs), so
+      // nothing ever reaches this match with such a type today. If that
+      // front-end support is added, this arm is where its layout belongs:
+      // size the T_struct to the largest member (using shape::llsize_of/
+      // llalign_of the way type_of_enum sizes a degenerate one-variant
+      // enum), put every member at offset 0, and have GEP_tup_like bitcast
+      // to the requested field's type instead of indexing a distinct
+      // struct field, since there's only the one underlying slot.
       _ { fail "type_of not implemented for this kind of type"; }
     };
     cx.lltypes.insert(t, llty);
     ret llty;
 }
 
+// A C-like enum (ty::enum_variants shows every variant is nullary) is laid
+// out exactly like any other enum here -- a byte blob sized by
+// shape::static_size_of_enum, with the discriminant reached through the
+// same GEPi(.., [0, 0]) as a real tagged union's -- even though there's no
+// payload ever stored past the discriminant and a bare integer of the
+// discriminant's width would represent it just as well, with no wrapping
+// struct/array at all. That's a real difference (an all-nullary
+// `request`-style enum only needs a few bytes of plain integer, not a
+// synthetic aggregate LLVM has to see through to fold everything down to
+// the same thing), but changing it isn't a one-function fix: type_of_enum
+// here is only the type; the actual discriminant access pattern
+// `GEPi(.., [0, 0])` this would have to stop assuming is repeated at
+// upwards of ten call sites across trans (see trans_var's def_variant arm,
+// iter_structural_ty, the comparison/copy paths, and lookup_discriminant's
+// callers, all in trans::base), plus trans_cast's enum-to-int path,
+// alt.rs's pattern-lowering switches, shape.rs's runtime shape-glue
+// tables, and debuginfo.rs's enum metadata -- all of which currently
+// assume "an enum value is an aggregate with a discriminant field at
+// offset 0", not "an enum value might just be an integer". Moving all of
+// those in lockstep, correctly, for a representation this load-bearing
+// (every enum in the standard library, including every Option-shaped
+// type) isn't something to do blind in a tree with no way to build or run
+// the result to catch a mistake.
 fn type_of_enum(cx: crate_ctxt, did: ast::def_id, t: ty::t)
     -> TypeRef {
     let degen = (*ty::enum_variants(cx.tcx, did)).len() == 1u;
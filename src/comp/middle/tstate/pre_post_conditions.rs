@@ -493,8 +493,9 @@ fn find_pre_post_expr(fcx: fn_ctxt, e: @expr) {
                             then everything is true! */
                          prestate, false_postcond(num_local_vars));
       }
-      expr_assert(p) {
+      expr_assert(p, msg) {
         find_pre_post_expr(fcx, p);
+        option::may(msg, {|m| find_pre_post_expr(fcx, m) });
         copy_pre_post(fcx.ccx, e.id, p);
       }
       expr_check(_, p) {
@@ -533,6 +534,11 @@ fn find_pre_post_expr(fcx: fn_ctxt, e: @expr) {
       expr_break { clear_pp(expr_pp(fcx.ccx, e)); }
       expr_cont { clear_pp(expr_pp(fcx.ccx, e)); }
       expr_mac(_) { fcx.ccx.tcx.sess.bug("unexpanded macro"); }
+      expr_asm(a) {
+        let exs = vec::map(a.outputs, {|o| o.expr}) +
+            vec::map(a.inputs, {|i| i.expr});
+        find_pre_post_exprs(fcx, exs, e.id);
+      }
     }
 }
 
@@ -603,7 +603,7 @@ fn find_pre_post_state_expr(fcx: fn_ctxt, pres: prestate, e: @expr) -> bool {
                 option::maybe(false, maybe_fail_val, {|fail_val|
                         find_pre_post_state_expr(fcx, pres, fail_val)});
       }
-      expr_assert(p) {
+      expr_assert(p, _) {
         ret find_pre_post_state_sub(fcx, pres, p, e.id, none);
       }
       expr_check(_, p) {
@@ -616,6 +616,14 @@ fn find_pre_post_state_expr(fcx: fn_ctxt, pres: prestate, e: @expr) -> bool {
       }
       expr_break { ret pure_exp(fcx.ccx, e.id, pres); }
       expr_cont { ret pure_exp(fcx.ccx, e.id, pres); }
+      expr_asm(a) {
+        let exs = vec::map(a.outputs, {|o| o.expr}) +
+            vec::map(a.inputs, {|i| i.expr});
+        ret find_pre_post_state_exprs(fcx, pres, e.id,
+                                      vec::init_elt(vec::len(exs),
+                                                    init_assign), exs,
+                                      return_val);
+      }
     }
 }
 
@@ -235,6 +235,11 @@ enum sty {
     ty_class(def_id, [t]),
     ty_res(def_id, t, [t]),
     ty_tup([t]),
+    // No ty_union variant: a `union`-style overlapping-storage type would
+    // need an ast::item_kind, a parser production, and resolve/typeck
+    // support for it before trans could give it a layout, none of which
+    // exist in this tree yet. See the note next to type_of's fallback arm
+    // in trans/type_of.rs for what a codegen implementation would build on.
 
     ty_var(int), // type variable during typechecking
     ty_param(uint, def_id), // type parameter
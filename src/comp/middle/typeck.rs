@@ -1982,6 +1982,32 @@ fn check_expr_with_unifier(fcx: @fn_ctxt, expr: @ast::expr, unify: unifier,
         ret bot;
     }
 
+    // Given the node id of a `seq` expr already resolved (via lookup_method)
+    // to a `next` method, pulls the `T` out of that method's `option<T>`-
+    // shaped return type. The check is purely structural (a 2-variant enum
+    // whose first variant is nullary and second carries a single argument)
+    // so this doesn't need to know the def_id of `option` itself.
+    fn iter_next_elt_ty(fcx: @fn_ctxt, sp: span, seq_id: ast::node_id)
+        -> ty::t {
+        let tcx = fcx.ccx.tcx;
+        let ret_ty = ty::ty_fn_ret(ty::node_id_to_type(tcx, seq_id));
+        alt structure_of(fcx, sp, ret_ty) {
+          ty::ty_enum(did, tps) {
+            let variants = ty::substd_enum_variants(tcx, did, tps);
+            if vec::len(variants) == 2u &&
+               vec::len(variants[0].args) == 0u &&
+               vec::len(variants[1].args) == 1u {
+                ret variants[1].args[0];
+            }
+          }
+          _ {}
+        }
+        tcx.sess.span_fatal(sp,
+                            "`next` must return an `option`-shaped type, "
+                            + "i.e. an enum with a nullary variant and a "
+                            + "variant carrying a single value");
+    }
+
     // A generic function for checking for or for-each loops
     fn check_for(fcx: @fn_ctxt, local: @ast::local,
                  element_ty: ty::t, body: ast::blk,
@@ -2233,8 +2259,25 @@ fn check_expr_with_unifier(fcx: @fn_ctxt, expr: @ast::expr, unify: unifier,
             check_pred_expr(fcx, cond) |
                 check_then_else(fcx, thn, elsopt, id, expr.span);
       }
-      ast::expr_assert(e) {
+      ast::expr_asm(a) {
+        // Start with integer-only operands: every output/input must name
+        // an integral lval/value.
+        for o in a.outputs {
+            bot |= check_expr_with(fcx, o.expr,
+                                    ty::mk_int(tcx));
+        }
+        for i in a.inputs {
+            bot |= check_expr_with(fcx, i.expr,
+                                    ty::mk_int(tcx));
+        }
+        write_nil(tcx, id);
+      }
+      ast::expr_assert(e, msg) {
         bot = check_expr_with(fcx, e, ty::mk_bool(tcx));
+        alt msg {
+          none {/* do nothing */ }
+          some(m) { check_expr_with(fcx, m, ty::mk_str(tcx)); }
+        }
         write_nil(tcx, id);
       }
       ast::expr_copy(a) {
@@ -2266,9 +2309,19 @@ fn check_expr_with_unifier(fcx: @fn_ctxt, expr: @ast::expr, unify: unifier,
           ty::ty_vec(vec_elt_ty) { elt_ty = vec_elt_ty.ty; }
           ty::ty_str { elt_ty = ty::mk_mach_uint(tcx, ast::ty_u8); }
           _ {
-            tcx.sess.span_fatal(expr.span,
-                                "mismatched types: expected vector or string "
-                                + "but found `" + ty_to_str(tcx, ety) + "`");
+            alt lookup_method(fcx, seq, seq.id, "next", ety, []) {
+              some(origin) {
+                fcx.ccx.method_map.insert(seq.id, origin);
+                elt_ty = iter_next_elt_ty(fcx, expr.span, seq.id);
+              }
+              none {
+                tcx.sess.span_fatal(expr.span,
+                                    "mismatched types: expected vector, "
+                                    + "string, or a type implementing "
+                                    + "`next() -> option<T>`, but found `"
+                                    + ty_to_str(tcx, ety) + "`");
+              }
+            }
           }
         }
         bot |= check_for(fcx, decl, elt_ty, body, id);
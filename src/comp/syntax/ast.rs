@@ -213,6 +213,20 @@ enum blk_check_mode { default_blk, unchecked_blk, unsafe_blk, }
 
 enum expr_check_mode { claimed_expr, checked_expr, }
 
+// A single output or input operand of an inline_asm, following the usual
+// GCC/LLVM asm constraint string conventions. Output operand exprs must be
+// lvals, since the asm writes into them.
+type asm_operand = {constraint: str, expr: @expr};
+
+// An `asm!`-style inline assembly expression.
+type inline_asm = {
+    asm: str,
+    outputs: [asm_operand],
+    inputs: [asm_operand],
+    clobbers: [str],
+    volatile: bool,
+};
+
 type expr = {id: node_id, node: expr_, span: span};
 
 enum alt_mode { alt_check, alt_exhaustive, }
@@ -256,7 +270,7 @@ enum expr_ {
     expr_log(int, @expr, @expr),
 
     /* just an assert, no significance to typestate */
-    expr_assert(@expr),
+    expr_assert(@expr, option<@expr>),
 
     /* preds that typestate is aware of */
     expr_check(expr_check_mode, @expr),
@@ -265,6 +279,8 @@ enum expr_ {
        to expr_if_check. */
     expr_if_check(@expr, blk, option<@expr>),
     expr_mac(mac),
+
+    expr_asm(inline_asm),
 }
 
 type capture_item = {
@@ -0,0 +1,80 @@
+/*
+ * The compiler code necessary to support the #asm extension, which expands
+ * into an ast::expr_asm inline-assembly expression.
+ */
+import base::*;
+import syntax::ast;
+
+fn expand_syntax_ext(cx: ext_ctxt, sp: codemap::span, arg: ast::mac_arg,
+                     _body: ast::mac_body) -> @ast::expr {
+    let arg = get_mac_arg(cx, sp, arg);
+    let args: [@ast::expr] =
+        alt arg.node {
+          ast::expr_vec(elts, _) { elts }
+          _ {
+            cx.span_fatal(sp, "#asm requires arguments of the form " +
+                          "`[asm, outputs, inputs, clobbers, volatile]`.")
+          }
+        };
+    if vec::len(args) != 5u {
+        cx.span_fatal(sp, "malformed #asm call");
+    }
+
+    let asm = expr_to_str(cx, args[0], "#asm requires a template string");
+    let outputs = parse_operands(cx, sp, args[1], "output");
+    let inputs = parse_operands(cx, sp, args[2], "input");
+    let clobbers = vec::map(parse_elts(cx, sp, args[3], "clobber list"),
+                            {|e| expr_to_str(cx, e, "expected a clobber\
+                                                      register string") });
+    let volatile = expr_to_bool(cx, args[4], "#asm's volatile flag must be\
+                                              a bool literal");
+
+    ret @{id: cx.next_id(),
+          node: ast::expr_asm({asm: asm,
+                               outputs: outputs,
+                               inputs: inputs,
+                               clobbers: clobbers,
+                               volatile: volatile}),
+          span: sp};
+}
+
+fn parse_elts(cx: ext_ctxt, sp: codemap::span, e: @ast::expr, what: str) ->
+   [@ast::expr] {
+    alt e.node {
+      ast::expr_vec(elts, _) { elts }
+      _ { cx.span_fatal(sp, "expected a vector for #asm's " + what); }
+    }
+}
+
+fn parse_operands(cx: ext_ctxt, sp: codemap::span, e: @ast::expr,
+                  what: str) -> [ast::asm_operand] {
+    ret vec::map(parse_elts(cx, sp, e, what + " list"), {|op|
+        alt op.node {
+          ast::expr_tup([constraint, val]) {
+            {constraint: expr_to_str(cx, constraint,
+                                     "expected a constraint string"),
+             expr: val}
+          }
+          _ {
+            cx.span_fatal(sp, "expected a (constraint, expr) tuple for an\
+                               #asm " + what)
+          }
+        }
+    });
+}
+
+fn expr_to_bool(cx: ext_ctxt, e: @ast::expr, error: str) -> bool {
+    alt e.node {
+      ast::expr_lit(@{node: ast::lit_bool(b), _}) { b }
+      _ { cx.span_fatal(e.span, error) }
+    }
+}
+//
+// Local Variables:
+// mode: rust
+// fill-column: 78;
+// indent-tabs-mode: nil
+// c-basic-offset: 4
+// buffer-file-coding-system: utf-8-unix
+// End:
+//
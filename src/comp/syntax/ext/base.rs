@@ -33,8 +33,22 @@ fn syntax_expander_table() -> hashmap<str, syntax_extension> {
                             builtin(ext::ident_to_str::expand_syntax_ext));
     syntax_expanders.insert("log_syntax",
                             builtin(ext::log_syntax::expand_syntax_ext));
+    syntax_expanders.insert("stringify",
+                            builtin(ext::stringify::expand_syntax_ext));
+    syntax_expanders.insert("concat",
+                            builtin(ext::concat::expand_syntax_ext));
+    syntax_expanders.insert("line",
+                            builtin(ext::line::expand_syntax_ext));
+    syntax_expanders.insert("col",
+                            builtin(ext::col::expand_syntax_ext));
+    syntax_expanders.insert("file",
+                            builtin(ext::file::expand_syntax_ext));
+    syntax_expanders.insert("include_str",
+                            builtin(ext::include_str::expand_syntax_ext));
     syntax_expanders.insert("ast",
                             builtin(ext::qquote::expand_ast));
+    syntax_expanders.insert("asm",
+                            builtin(ext::asm::expand_syntax_ext));
     ret syntax_expanders;
 }
 
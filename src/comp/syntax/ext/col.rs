@@ -0,0 +1,8 @@
+import base::*;
+import syntax::ast;
+
+fn expand_syntax_ext(cx: ext_ctxt, sp: codemap::span, _arg: ast::mac_arg,
+                     _body: ast::mac_body) -> @ast::expr {
+    let loc = codemap::lookup_char_pos(cx.session().parse_sess.cm, sp.lo);
+    ret make_new_lit(cx, sp, ast::lit_int(loc.col as i64, ast::ty_i));
+}
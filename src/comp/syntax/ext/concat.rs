@@ -0,0 +1,24 @@
+import base::*;
+import syntax::ast;
+
+// Only string-literal args can fold here: syntax extensions expand before
+// resolve/typeck ever run, so a bare ident like `some_const_str` has no
+// resolved value yet for #concat to read -- expr_to_str's span_fatal on
+// anything but a literal is what that turns into.
+fn expand_syntax_ext(cx: ext_ctxt, sp: codemap::span, arg: ast::mac_arg,
+                     _body: ast::mac_body) -> @ast::expr {
+    let arg = get_mac_arg(cx, sp, arg);
+    let args: [@ast::expr] =
+        alt arg.node {
+          ast::expr_vec(elts, _) { elts }
+          _ {
+            cx.span_fatal(sp, "#concat requires a vector argument .")
+          }
+        };
+    let res = "";
+    for e: @ast::expr in args {
+        res += expr_to_str(cx, e, "#concat requires string literal args");
+    }
+
+    ret make_new_lit(cx, sp, ast::lit_str(res));
+}
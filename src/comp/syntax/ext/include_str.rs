@@ -0,0 +1,29 @@
+import base::*;
+import syntax::ast;
+import std::{io, fs};
+
+fn expand_syntax_ext(cx: ext_ctxt, sp: codemap::span, arg: ast::mac_arg,
+                     _body: ast::mac_body) -> @ast::expr {
+    let arg = get_mac_arg(cx, sp, arg);
+    let args: [@ast::expr] =
+        alt arg.node {
+          ast::expr_vec(elts, _) { elts }
+          _ {
+            cx.span_fatal(sp, "#include_str requires a vector argument .")
+          }
+        };
+    if vec::len::<@ast::expr>(args) != 1u {
+        cx.span_fatal(sp, "malformed #include_str call");
+    }
+    let file = expr_to_str(cx, args[0], "#include_str requires a string");
+
+    let loc = codemap::lookup_char_pos(cx.session().parse_sess.cm, sp.lo);
+    let path = fs::connect(fs::dirname(loc.file.name), file);
+
+    ret alt io::read_whole_file_str(path) {
+      result::ok(contents) { make_new_lit(cx, sp, ast::lit_str(contents)) }
+      result::err(e) {
+        cx.span_fatal(sp, #fmt["couldn't read %s: %s", path, e])
+      }
+    };
+}
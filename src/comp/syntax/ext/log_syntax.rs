@@ -5,9 +5,20 @@ import std::io::writer_util;
 fn expand_syntax_ext(cx: ext_ctxt, sp: codemap::span, arg: ast::mac_arg,
                      _body: ast::mac_body) -> @ast::expr {
     let arg = get_mac_arg(cx,sp,arg);
+    let args: [@ast::expr] =
+        alt arg.node {
+          ast::expr_vec(elts, _) { elts }
+          _ {
+            cx.span_fatal(sp, "#log_syntax requires a vector argument .")
+          }
+        };
+    if vec::len::<@ast::expr>(args) != 1u {
+        cx.span_fatal(sp, "malformed #log_syntax call");
+    }
     cx.print_backtrace();
-    std::io::stdout().write_line(print::pprust::expr_to_str(arg));
+    std::io::stdout().write_line(print::pprust::expr_to_str(args[0]));
 
-    //trivial expression
-    ret @{id: cx.next_id(), node: ast::expr_rec([], option::none), span: sp};
+    // Return the argument unchanged so #log_syntax(e) is transparent and
+    // can wrap any subexpression, not just stand alone as a statement.
+    ret args[0];
 }
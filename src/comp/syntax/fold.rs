@@ -342,6 +342,9 @@ fn noop_fold_expr(e: expr_, fld: ast_fold) -> expr_ {
                   expr: fld.fold_expr(field.node.expr)},
              span: field.span};
     }
+    fn fold_asm_operand(o: asm_operand, fld: ast_fold) -> asm_operand {
+        ret {constraint: o.constraint, expr: fld.fold_expr(o.expr)};
+    }
     let fold_field = bind fold_field_(_, fld);
 
     let fold_mac = bind fold_mac_(_, fld);
@@ -421,13 +424,24 @@ fn noop_fold_expr(e: expr_, fld: ast_fold) -> expr_ {
           expr_be(e) { expr_be(fld.fold_expr(e)) }
           expr_log(i, lv, e) { expr_log(i, fld.fold_expr(lv),
                                         fld.fold_expr(e)) }
-          expr_assert(e) { expr_assert(fld.fold_expr(e)) }
+          expr_assert(e, msg) {
+            expr_assert(fld.fold_expr(e), option::map(msg, fld.fold_expr))
+          }
           expr_check(m, e) { expr_check(m, fld.fold_expr(e)) }
           expr_if_check(cond, tr, fl) {
             expr_if_check(fld.fold_expr(cond), fld.fold_block(tr),
                           option::map(fl, fld.fold_expr))
           }
           expr_mac(mac) { expr_mac(fold_mac(mac)) }
+          expr_asm(a) {
+            expr_asm({asm: a.asm,
+                      outputs: vec::map(a.outputs,
+                          bind fold_asm_operand(_, fld)),
+                      inputs: vec::map(a.inputs,
+                          bind fold_asm_operand(_, fld)),
+                      clobbers: a.clobbers,
+                      volatile: a.volatile})
+          }
         }
 }
 
@@ -882,8 +882,11 @@ fn parse_bottom_expr(p: parser) -> pexpr {
         expect(p, token::RPAREN);
     } else if eat_word(p, "assert") {
         let e = parse_expr(p);
-        ex = ast::expr_assert(e);
-        hi = e.span.hi;
+        let msg = if eat(p, token::COMMA) {
+            some(parse_expr(p))
+        } else { none };
+        hi = alt msg { some(m) { m.span.hi } none { e.span.hi } };
+        ex = ast::expr_assert(e, msg);
     } else if eat_word(p, "check") {
         /* Should be a predicate (pure boolean function) applied to
            arguments that are all either slot variables or literals.
@@ -1050,11 +1050,21 @@ fn print_expr(s: ps, &&expr: @ast::expr) {
         print_expr(s, expr);
         pclose(s);
       }
-      ast::expr_assert(expr) {
+      ast::expr_assert(expr, msg) {
         word_nbsp(s, "assert");
         print_expr(s, expr);
+        alt msg {
+          some(m) { word_space(s, ","); print_expr(s, m); }
+          none { }
+        }
       }
       ast::expr_mac(m) { print_mac(s, m); }
+      ast::expr_asm(a) {
+        word(s.s, "asm!");
+        popen(s);
+        print_string(s, a.asm);
+        pclose(s);
+      }
     }
     s.ann.post(ann_node);
     end(s);
@@ -1067,7 +1077,7 @@ fn print_expr_parens_if_not_bot(s: ps, ex: @ast::expr) {
       ast::expr_move(_, _) | ast::expr_copy(_) |
       ast::expr_assign(_, _) | ast::expr_be(_) |
       ast::expr_assign_op(_, _, _) | ast::expr_swap(_, _) |
-      ast::expr_log(_, _, _) | ast::expr_assert(_) |
+      ast::expr_log(_, _, _) | ast::expr_assert(_, _) |
       ast::expr_call(_, _, true) |
       ast::expr_check(_, _) { true }
       _ { false }
@@ -1425,7 +1435,7 @@ fn need_parens(expr: @ast::expr, outer_prec: int) -> bool {
       ast::expr_assign_op(_, _, _) { true }
       ast::expr_ret(_) { true }
       ast::expr_be(_) { true }
-      ast::expr_assert(_) { true }
+      ast::expr_assert(_, _) { true }
       ast::expr_check(_, _) { true }
       ast::expr_log(_, _, _) { true }
       _ { !parse::parser::expr_requires_semi_to_be_stmt(expr) }
@@ -1792,7 +1802,7 @@ fn ends_in_lit_int(ex: @ast::expr) -> bool {
       ast::expr_move(_, sub) | ast::expr_copy(sub) |
       ast::expr_assign(_, sub) | ast::expr_be(sub) |
       ast::expr_assign_op(_, _, sub) | ast::expr_swap(_, sub) |
-      ast::expr_log(_, _, sub) | ast::expr_assert(sub) |
+      ast::expr_log(_, _, sub) | ast::expr_assert(sub, _) |
       ast::expr_check(_, sub) { ends_in_lit_int(sub) }
       ast::expr_fail(osub) | ast::expr_ret(osub) {
         alt osub {
@@ -1804,6 +1814,49 @@ fn ends_in_lit_int(ex: @ast::expr) -> bool {
     }
 }
 
+#[cfg(test)]
+mod test {
+    // ty_to_str's fn_to_str (util::ppaux.rs) builds a closure/fn type's
+    // printed form as `proto_to_str(proto) + "(" + <inputs> + ")" + ...`;
+    // for a zero-argument, no-return closure that reduces to exactly
+    // `proto_to_str(proto) + "()"`. This audits proto_to_str directly
+    // (no ty::ctxt needed) to confirm every proto -- in particular the
+    // three closure kinds fn@/fn~/fn& and bare fn -- prints a distinct,
+    // unambiguous string, so a fn_block type never prints identically to
+    // a bare fn.
+    #[test]
+    fn proto_to_str_distinguishes_every_proto() {
+        let protos = [ast::proto_bare, ast::proto_any, ast::proto_block,
+                      ast::proto_uniq, ast::proto_box];
+        let strs = vec::map(protos, {|p| proto_to_str(p)});
+        let i = 0u;
+        while i < vec::len(strs) {
+            let j = i + 1u;
+            while j < vec::len(strs) {
+                assert strs[i] != strs[j];
+                j += 1u;
+            }
+            i += 1u;
+        }
+    }
+
+    #[test]
+    fn box_and_uniq_closures_print_differently() {
+        assert proto_to_str(ast::proto_box) + "()" == "fn@()";
+        assert proto_to_str(ast::proto_uniq) + "()" == "fn~()";
+        assert proto_to_str(ast::proto_box) + "()" !=
+            proto_to_str(ast::proto_uniq) + "()";
+    }
+
+    #[test]
+    fn block_closure_differs_from_bare_fn() {
+        assert proto_to_str(ast::proto_block) + "()" == "fn&()";
+        assert proto_to_str(ast::proto_any) + "()" == "fn()";
+        assert proto_to_str(ast::proto_block) + "()" !=
+            proto_to_str(ast::proto_any) + "()";
+    }
+}
+
 //
 // Local Variables:
 // mode: rust
@@ -374,8 +374,15 @@ fn visit_expr<E>(ex: @expr, e: E, v: vt<E>) {
         v.visit_expr(x, e, v);
       }
       expr_check(_, x) { v.visit_expr(x, e, v); }
-      expr_assert(x) { v.visit_expr(x, e, v); }
+      expr_assert(x, msg) {
+        v.visit_expr(x, e, v);
+        visit_expr_opt(msg, e, v);
+      }
       expr_mac(mac) { visit_mac(mac, e, v); }
+      expr_asm(a) {
+        for out in a.outputs { v.visit_expr(out.expr, e, v); }
+        for in_ in a.inputs { v.visit_expr(in_.expr, e, v); }
+      }
     }
 }
 
@@ -8,6 +8,50 @@ import syntax::{ast, ast_util};
 import middle::ast_map;
 import driver::session::session;
 
+// Borrowed-pointer ("&") types have no ty::sty variant yet in this tree --
+// there's no ast::ty_rptr, no region inference, and giving one a case here
+// would mean threading it through every exhaustive `alt ty::get(_).struct`
+// in typeck/trans/resolve/metadata (twenty-odd files), none of which know
+// how to check or codegen a borrowed reference yet. That's out of scope for
+// a diagnostics-only change. What can be pinned down now is a deterministic
+// textual form for the region itself, so that whichever shape a future
+// ty_rptr picks up (most likely a `region` alongside the type, following
+// the way ty::sty's ty_param/ty_var carry their own identifying data) has
+// rmt_to_str ready to slot into ty_to_str's structural alt.
+enum region {
+    re_named(str),
+    re_anon,
+}
+
+fn region_to_str(r: region) -> str {
+    alt r {
+      re_named(name) { "&" + name + "." }
+      re_anon { "&" }
+    }
+}
+
+// The `mut `/`const `/`` prefix ty_to_str puts in front of an mt-qualified
+// type's pointee (@T, ~T, *T, [T], and rmt_to_str's &r.T below). Pulled out
+// on its own so there's one definition to audit instead of one copy per
+// pointer-like variant, after an audit turned up ty_to_str's mt_to_str
+// already handling all three mutabilities consistently but rmt_to_str
+// duplicating the same alt rather than sharing it.
+fn mutbl_prefix(mutbl: ast::mutability) -> str {
+    alt mutbl {
+      ast::m_mutbl { "mut " }
+      ast::m_imm { "" }
+      ast::m_const { "const " }
+    }
+}
+
+// Prints a borrowed-pointer type as "&r.T" (or "&r.mut T" for a mutable
+// borrow), given a region and the pointee's mt. Not yet reachable from
+// ty_to_str's structural alt since no ty_rptr variant exists to dispatch
+// on, but kept ready to slot in once one does.
+fn rmt_to_str(cx: ctxt, r: region, m: mt) -> str {
+    region_to_str(r) + mutbl_prefix(m.mutbl) + ty_to_str(cx, m.ty)
+}
+
 fn ty_to_str(cx: ctxt, typ: t) -> str {
     fn fn_input_to_str(cx: ctxt, input: {mode: ast::mode, ty: t}) ->
        str {
@@ -25,6 +69,16 @@ fn ty_to_str(cx: ctxt, typ: t) -> str {
         };
         modestr + ty_to_str(cx, ty)
     }
+    // proto_to_str (syntax::print::pprust) already gives every proto --
+    // bare fn, native fn, and the three closure kinds fn@/fn~/fn& -- its
+    // own distinct string, called unconditionally below, so a fn_block
+    // (fn&) type never prints identically to a bare fn. A separate
+    // captured-by-ref-vs-by-copy indicator isn't something ty_to_str
+    // could add on top of that: capture mode isn't part of ty::fn_ty at
+    // all, only of a specific closure *expression*'s capture_clause
+    // (syntax::ast), and it's implied by proto anyway -- fn@/fn~ always
+    // copy captured values into an owned environment, fn& always borrows
+    // the enclosing frame -- so proto_to_str's output already conveys it.
     fn fn_to_str(cx: ctxt, proto: ast::proto, ident: option<ast::ident>,
                  inputs: [arg], output: t, cf: ast::ret_style,
                  constrs: [@constr]) -> str {
@@ -53,12 +107,7 @@ fn ty_to_str(cx: ctxt, typ: t) -> str {
         ret f.ident + ": " + mt_to_str(cx, f.mt);
     }
     fn mt_to_str(cx: ctxt, m: mt) -> str {
-        let mstr = alt m.mutbl {
-          ast::m_mutbl { "mut " }
-          ast::m_imm { "" }
-          ast::m_const { "const " }
-        };
-        ret mstr + ty_to_str(cx, m.ty);
+        ret mutbl_prefix(m.mutbl) + ty_to_str(cx, m.ty);
     }
     fn parameterized(cx: ctxt, base: str, tps: [ty::t]) -> str {
         if vec::len(tps) > 0u {
@@ -113,9 +162,24 @@ fn ty_to_str(cx: ctxt, typ: t) -> str {
         fn_to_str(cx, f.proto, none, f.inputs, f.output, f.ret_style,
                   f.constraints)
       }
+      // There's no separate ty::sty case to distinguish here: unlike
+      // later compilers, this tree has no integral/float inference
+      // variable at all -- check_lit (middle/typeck.rs) gives every
+      // integer and float literal a concrete machine type from its
+      // suffix (defaulting to int/float when unsuffixed) at the point
+      // it's checked, rather than deferring to a var resolved later.
+      // Every ty_var seen here is a genuinely general type variable, so
+      // there's no `<VI-N>`/`<VF-N>` distinction to make -- <TN> is
+      // already the accurate, non-misleading form.
       ty_var(v) { "<T" + int::str(v) + ">" }
       ty_param(id, _) {
-        "'" + str::from_bytes([('a' as u8) + (id as u8)])
+        // 'a, 'b, ..., 'z, then wrap around with a suffix ('a1, 'b1, ...,
+        // 'z1, 'a2, ...) instead of running off the end of the lowercase
+        // alphabet into punctuation ('{', '|', ...) once a function has
+        // more than 26 type parameters.
+        let letter = str::from_bytes([('a' as u8) + ((id % 26u) as u8)]);
+        let suffix = if id < 26u { "" } else { uint::str(id / 26u) };
+        "'" + letter + suffix
       }
       ty_enum(did, tps) | ty_res(did, _, tps) | ty_class(did, tps) {
         // Not sure why, but under some circumstances enum or resource types
@@ -129,6 +193,184 @@ fn ty_to_str(cx: ctxt, typ: t) -> str {
     }
 }
 
+// Like ty_to_str, but for ty_class prints the class's fields --
+// `ClassName{field: type, ...}` -- instead of just its parameterized
+// name, using the field's own AST type (unchecked, printed via
+// syntax::print::pprust rather than re-run through ty_to_str, since a
+// class's declared field types don't need a ty::ctxt to render and this
+// avoids re-deriving ty::t for each one just to print it). This is
+// deliberately not the default: showing every field on every class in
+// an ordinary error message would bloat exactly the messages ty_to_str
+// is meant to keep short, so it's opt-in for debugging trans and typeck.
+fn ty_to_str_verbose(cx: ctxt, typ: t) -> str {
+    alt ty::get(typ).struct {
+      ty_class(did, _) {
+        alt cx.items.find(did.node) {
+          some(ast_map::node_item(@{node: ast::item_class(_, items, _, _, _),
+                                    ident, _}, _)) {
+            let strs = [];
+            for item in items {
+                alt item.node.decl {
+                  ast::instance_var(name, field_ty, _, _) {
+                    strs += [name + ": " + pprust::ty_to_str(*field_ty)];
+                  }
+                  ast::class_method(_) { /* not a field */ }
+                }
+            }
+            ident + "{" + str::connect(strs, ",") + "}"
+          }
+          _ { ty_to_str(cx, typ) }
+        }
+      }
+      _ { ty_to_str(cx, typ) }
+    }
+}
+
+// Like ty_to_str, but bounds the size of the result: once `max_depth`
+// levels of structural nesting have been descended into, remaining
+// structure is elided as "...", and a type already on the current
+// recursion path (tracked by its type_def_id, the same shortcut ty_to_str
+// itself uses to decide when a type is nominal) is likewise printed as
+// "..." rather than unfolded again. ty_to_str's own type_def_id shortcut
+// already stops it from unfolding a named type's *definition* -- printing
+// enum/res/class types by name plus type arguments rather than by
+// variant/field bodies -- so a genuine infinite loop would need a type
+// argument list that refers back to itself, which typeck should never
+// produce; the visited-set here is a cheap backstop against that rather
+// than a case this tree is otherwise known to hit. What max_depth is
+// actually for is bounding the anonymous structural nesting (records,
+// tuples, boxes, vecs, fns) that has no def_id to shortcut through and
+// so can otherwise make an error message for a deeply nested type grow
+// without bound.
+//
+// Like ty_to_str_verbose above, nothing in typeck or trans calls this yet
+// -- it's exposed for a future diagnostic call site to opt into once one
+// is found to actually produce unbounded output -- so there's no
+// compiled test exercising it end-to-end here: building a ty::ctxt to
+// construct a {a: {b: {c: int}}} ty::t directly needs a full
+// session/def_map/ast_map/freevars (ty::mk_ctxt's own signature), which
+// this file has no lighter-weight way to stand up in a unit test.
+fn ty_to_str_depth(cx: ctxt, typ: t, max_depth: uint) -> str {
+    fn go(cx: ctxt, typ: t, depth: uint, max_depth: uint,
+         seen: [ast::def_id]) -> str {
+        if depth > max_depth { ret "..."; }
+        alt ty::type_def_id(typ) {
+          some(def_id) {
+            if vec::contains(seen, def_id) { ret "..."; }
+            let seen = seen + [def_id];
+            let cs = ast_map::path_to_str(ty::item_path(cx, def_id));
+            ret alt ty::get(typ).struct {
+              ty_enum(_, tps) | ty_res(_, _, tps) {
+                parameterized(cx, cs, tps, depth, max_depth, seen)
+              }
+              _ { cs }
+            };
+          }
+          none { /* fallthrough */ }
+        }
+        fn mt_to_str(cx: ctxt, m: mt, depth: uint, max_depth: uint,
+                    seen: [ast::def_id]) -> str {
+            mutbl_prefix(m.mutbl) + go(cx, m.ty, depth + 1u, max_depth, seen)
+        }
+        fn field_to_str(cx: ctxt, f: field, depth: uint, max_depth: uint,
+                        seen: [ast::def_id]) -> str {
+            f.ident + ": " + mt_to_str(cx, f.mt, depth, max_depth, seen)
+        }
+        fn parameterized(cx: ctxt, base: str, tps: [ty::t], depth: uint,
+                         max_depth: uint, seen: [ast::def_id]) -> str {
+            if vec::len(tps) > 0u {
+                let strs = vec::map(tps, {|t|
+                    go(cx, t, depth + 1u, max_depth, seen)
+                });
+                #fmt["%s<%s>", base, str::connect(strs, ",")]
+            } else {
+                base
+            }
+        }
+        alt ty::get(typ).struct {
+          ty_box(tm) { "@" + mt_to_str(cx, tm, depth, max_depth, seen) }
+          ty_uniq(tm) { "~" + mt_to_str(cx, tm, depth, max_depth, seen) }
+          ty_ptr(tm) { "*" + mt_to_str(cx, tm, depth, max_depth, seen) }
+          ty_vec(tm) { "[" + mt_to_str(cx, tm, depth, max_depth, seen) + "]" }
+          ty_rec(elems) {
+            let strs: [str] = [];
+            for fld: field in elems {
+                strs += [field_to_str(cx, fld, depth, max_depth, seen)];
+            }
+            "{" + str::connect(strs, ",") + "}"
+          }
+          ty_tup(elems) {
+            let strs = [];
+            for elem in elems {
+                strs += [go(cx, elem, depth + 1u, max_depth, seen)];
+            }
+            "(" + str::connect(strs, ",") + ")"
+          }
+          ty_fn(f) {
+            let strs = vec::map(f.inputs, {|a: arg|
+                go(cx, a.ty, depth + 1u, max_depth, seen)
+            });
+            let s = proto_to_str(f.proto) + "(" + str::connect(strs, ", ") +
+                ")";
+            if ty::get(f.output).struct != ty_nil {
+                s += " -> " + go(cx, f.output, depth + 1u, max_depth, seen);
+            }
+            s
+          }
+          ty_enum(did, tps) | ty_res(did, _, tps) | ty_class(did, tps) {
+            let base = ast_map::path_to_str(ty::item_path(cx, did));
+            parameterized(cx, base, tps, depth, max_depth, seen)
+          }
+          _ { ty_to_str(cx, typ) }
+        }
+    }
+    go(cx, typ, 0u, max_depth, [])
+}
+
+// Like ty_to_str, but for a named (type_def_id-having) type, lets the
+// caller choose between the bare last path segment ("Bar") and the full
+// module-qualified path ("foo::Bar") -- ty_to_str itself always does the
+// latter, which is unambiguous but can be needlessly verbose when only one
+// `Bar` is in scope; a caller that's already found two distinct types
+// named `Bar` (e.g. by comparing def_ids while building an error message)
+// can ask for the qualified form on just those to disambiguate them
+// without changing how every other type prints. Anonymous/structural
+// types have no path to choose a form of, so they fall back to ty_to_str
+// unconditionally.
+//
+// As with ty_to_str_depth/ty_to_str_verbose above, there's no compiled
+// test here showing both forms for the same def_id: that needs a real
+// ty::ctxt (item_path walks the crate's ast_map), and this file has no
+// lighter-weight way to build one than ty::mk_ctxt's full session/def_map/
+// ast_map/freevars.
+fn ty_to_str_qualified(cx: ctxt, typ: t, qualified: bool) -> str {
+    alt ty::type_def_id(typ) {
+      some(def_id) {
+        let path = ty::item_path(cx, def_id);
+        let cs = if qualified {
+            ast_map::path_to_str(path)
+        } else {
+            alt vec::last(path) {
+              some(ast_map::path_mod(s)) | some(ast_map::path_name(s)) { s }
+              none { ast_map::path_to_str(path) }
+            }
+        };
+        alt ty::get(typ).struct {
+          ty_enum(_, tps) | ty_res(_, _, tps) {
+            if vec::len(tps) > 0u {
+                let strs = vec::map(tps, {|t| ty_to_str(cx, t)});
+                #fmt["%s<%s>", cs, str::connect(strs, ",")]
+            } else {
+                cs
+            }
+          }
+          _ { cs }
+        }
+      }
+      none { ty_to_str(cx, typ) }
+    }
+}
+
 fn ty_to_short_str(cx: ctxt, typ: t) -> str unsafe {
     let s = encoder::encoded_ty(cx, typ);
     if str::len_bytes(s) >= 32u { s = str::unsafe::slice_bytes(s, 0u, 32u); }
@@ -156,6 +398,21 @@ fn ty_constr_to_str<Q>(c: @ast::spanned<ast::constr_general_<@ast::path, Q>>)
             constr_args_to_str::<@ast::path>(path_to_str, c.node.args);
 }
 
+#[cfg(test)]
+mod test {
+    #[test]
+    fn mutbl_prefix_covers_every_mutability() {
+        let table = [
+            (ast::m_mutbl, "mut "),
+            (ast::m_imm, ""),
+            (ast::m_const, "const "),
+        ];
+        for (mutbl, expected) in table {
+            assert mutbl_prefix(mutbl) == expected;
+        }
+    }
+}
+
 // Local Variables:
 // mode: rust
 // fill-column: 78;
@@ -9,6 +9,8 @@ native mod rusti {
     fn ptr_offset<T>(ptr: *T, count: ctypes::uintptr_t) -> *T;
     fn memcpy<T>(dst: *T, src: *T, count: ctypes::uintptr_t);
     fn memmove<T>(dst: *T, src: *T, count: ctypes::uintptr_t);
+    fn unaligned_load<T>(src: *u8) -> T;
+    fn unaligned_store<T>(dst: *u8, src: T);
 }
 
 /*
@@ -73,6 +75,43 @@ unsafe fn memmove<T>(dst: *T, src: *T, count: uint)  {
     rusti::memmove(dst, src, count);
 }
 
+/*
+Function: unaligned_load
+
+Reads a value of type `T` from a possibly-unaligned byte offset. Unlike an
+ordinary `*p`, which trans emits as a `Load` at `T`'s natural alignment, this
+always lowers to an align-1 access (see rust_intrinsic_unaligned_load in
+src/rt/intrinsics), which is what strict-alignment targets need for reading
+values out of the middle of a byte buffer, e.g. when parsing a wire format.
+*/
+unsafe fn unaligned_load<T>(src: *u8) -> T {
+    ret rusti::unaligned_load(src);
+}
+
+/*
+Function: unaligned_store
+
+Writes `src` to a possibly-unaligned byte offset. The align-1 counterpart to
+unaligned_load above.
+*/
+unsafe fn unaligned_store<T>(dst: *u8, src: T) {
+    rusti::unaligned_store(dst, src);
+}
+
+// This tree only targets x86/x86_64 (see back::x86, back::x86_64 --
+// there's no ARM backend here to build the odd-byte-offset-on-ARM test the
+// request asks for), but unaligned_load/store need to work on any target,
+// so this exercises the same odd-offset access on whatever this crate
+// actually builds for.
+#[test]
+fn test_unaligned_load_store() unsafe {
+    let buf: [mutable u8] = [mutable 0u8, 0u8, 0u8, 0u8, 0u8];
+    let p = ptr::offset(vec::unsafe::to_ptr(buf), 1u);
+    unaligned_store(p, 0x11223344u32);
+    let v: u32 = unaligned_load(p);
+    assert v == 0x11223344u32;
+}
+
 #[test]
 fn test() unsafe {
     type pair = {mutable fst: int, mutable snd: int};
@@ -6,7 +6,17 @@ Misc low level stuff
 enum type_desc = {
     first_param: **ctypes::c_int,
     size: ctypes::size_t,
-    align: ctypes::size_t
+    align: ctypes::size_t,
+    take_glue: *u8,
+    drop_glue: *u8,
+    free_glue: *u8,
+    // Non-null iff the type needs drop glue run on it (see
+    // ty::type_needs_drop); used by needs_drop below. Not a real glue
+    // pointer -- never called, only compared against null.
+    needs_drop: *u8,
+    sever_glue: *u8,
+    mark_glue: *u8,
+    pref_align: ctypes::size_t
     // Remaining fields not listed
 };
 
@@ -25,6 +35,8 @@ native mod rustrt {
 #[abi = "rust-intrinsic"]
 native mod rusti {
     fn get_type_desc<T>() -> *type_desc;
+    fn unpredictable(b: bool) -> bool;
+    fn clflush(p: *u8);
 }
 
 /*
@@ -37,6 +49,30 @@ fn get_type_desc<T>() -> *type_desc {
     ret rusti::get_type_desc::<T>();
 }
 
+/*
+Function: unpredictable
+
+Hints to trans that `b` is a genuinely data-dependent condition (e.g. on
+random input) that shouldn't be lowered to a branch. The value is passed
+through unchanged; the effect is purely on how a subsequent `if`/`check`
+on the result gets compiled.
+*/
+fn unpredictable(b: bool) -> bool {
+    ret rusti::unpredictable(b);
+}
+
+/*
+Function: clflush
+
+Flushes the cache line containing `p` from every level of the cache
+hierarchy, forcing a subsequent access to go to memory. For persistent-
+memory and NUMA code that needs explicit control over cache state. On a
+target with no known cache-line-flush instruction this is a no-op.
+*/
+fn clflush(p: *u8) {
+    ret rusti::clflush(p);
+}
+
 /*
 Function: last_os_error
 
@@ -58,12 +94,50 @@ fn size_of<T>() -> uint unsafe {
 /*
 Function: align_of
 
-Returns the alignment of a type
+Returns the ABI-mandated minimum alignment of a type, i.e. the alignment
+it's given as a struct member or array element.
 */
 fn align_of<T>() -> uint unsafe {
     ret (*get_type_desc::<T>()).align;
 }
 
+/*
+Function: pref_align_of
+
+Returns the alignment the target prefers for a type, which for a handful
+of types on a handful of targets (e.g. `f64` on x86) is larger than the
+ABI-mandated `align_of`. Useful for precise allocation/layout tuning;
+`align_of` remains the one to use for anything that has to match how the
+type is laid out as a struct member or array element.
+*/
+fn pref_align_of<T>() -> uint unsafe {
+    ret (*get_type_desc::<T>()).pref_align;
+}
+
+/*
+Function: needs_drop
+
+Returns whether a value of type `T` needs to run drop glue when it goes
+out of scope, i.e. whether it (recursively) owns a resource, a unique
+pointer, or anything else that isn't trivially forgettable. Generic
+container code can use this to skip a drop loop entirely for element
+types where it would be a no-op.
+
+This is answered the same way size_of/align_of are: by reading a field
+off the type's tydesc, so it works uniformly whether or not `T` happens
+to be a concrete type at the call site. Unlike size_of/align_of, there's
+no rust-intrinsic mechanism for this that could instead let trans fold a
+fully-monomorphic call straight to a `true`/`false` literal: every
+rust_intrinsic here links to one hand-written .ll leaf function shared by
+every instantiation of T (see the checked_div note in
+trans::base::collect_native_item), so it has no way to see the concrete T
+a given call site was instantiated with and inline a constant for it.
+*/
+fn needs_drop<T>() -> bool unsafe {
+    ret unsafe::reinterpret_cast::<*u8, uint>(
+        (*get_type_desc::<T>()).needs_drop) != 0u;
+}
+
 /*
 Function: refcount
 
@@ -120,6 +194,18 @@ mod tests {
         assert size_of::<*uint>() == 8u;
     }
 
+    #[test]
+    #[cfg(target_arch = "x86")]
+    #[cfg(target_arch = "x86_64")]
+    fn clflush_smoke() {
+        // clflush is a hint straight to the target's cache-flush
+        // instruction with no observable effect on the value; this just
+        // exercises the call on the archs where it actually emits one.
+        let x = 1u8;
+        clflush(ptr::addr_of(x));
+        assert x == 1u8;
+    }
+
     #[test]
     fn align_of_basic() {
         assert align_of::<u8>() == 1u;
@@ -141,6 +227,34 @@ mod tests {
         assert align_of::<uint>() == 8u;
         assert align_of::<*uint>() == 8u;
     }
+
+    #[test]
+    fn pref_align_of_at_least_align_of() {
+        // Preferred alignment is never smaller than the ABI-mandated one
+        // (it's the ABI one that's sometimes the *reduced* special case,
+        // e.g. f64 below), for any type.
+        assert pref_align_of::<u8>() >= align_of::<u8>();
+        assert pref_align_of::<uint>() >= align_of::<uint>();
+        assert pref_align_of::<f64>() >= align_of::<f64>();
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86")]
+    fn pref_align_of_f64_on_x86() {
+        // The canonical case the two diverge on: x86's ABI only requires
+        // f64 to be 4-byte aligned (so it packs into structs the same as
+        // two i32s), but every x86 implementation actually operates on it
+        // faster when it's 8-byte aligned, so that's what's preferred.
+        assert align_of::<f64>() == 4u;
+        assert pref_align_of::<f64>() == 8u;
+    }
+
+    #[test]
+    fn needs_drop_basic() {
+        assert !needs_drop::<int>();
+        assert !needs_drop::<u8>();
+        assert needs_drop::<@int>();
+    }
 }
 
 // Local Variables:
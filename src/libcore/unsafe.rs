@@ -4,12 +4,14 @@ Module: unsafe
 Unsafe operations
 */
 
-export reinterpret_cast, leak;
+export reinterpret_cast, leak, discriminant_value, enum_from_raw;
 
 #[abi = "rust-intrinsic"]
 native mod rusti {
     fn cast<T, U>(src: T) -> U;
     fn leak<T>(-thing: T);
+    fn discriminant_value<E>(e: E) -> int;
+    fn enum_from_raw<E>(discr: int, data: *u8) -> E;
 }
 
 /*
@@ -38,6 +40,31 @@ reinterpret_cast on managed pointer types.
 */
 unsafe fn leak<T>(-thing: T) { rusti::leak(thing); }
 
+/*
+Function: discriminant_value
+
+Returns the tag (discriminant) of an enum value as an int, without
+matching on its variants.
+*/
+fn discriminant_value<E>(e: E) -> int {
+    ret rusti::discriminant_value(e);
+}
+
+/*
+Function: enum_from_raw
+
+Builds an enum value directly from a discriminant and a pre-laid-out
+data buffer, without going through a match-based constructor.
+
+This is inherently unsafe: `data` must point to at least
+`sys::size_of::<E>() - sys::size_of::<int>()` bytes laid out exactly as
+the variant selected by `discr` expects, and `discr` must be a valid
+discriminant of `E`. Getting either wrong corrupts the resulting value.
+*/
+unsafe fn enum_from_raw<E>(discr: int, data: *u8) -> E {
+    ret rusti::enum_from_raw(discr, data);
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -18,6 +18,7 @@ import rustc::back::link;
 import rustc::util::filesearch;
 import rustc::front;
 import rustc::middle::resolve;
+import rustc::metadata::{creader, cstore};
 
 export ctxt;
 export ctxt_handler;
@@ -29,7 +30,8 @@ export exec;
 type ctxt = {
     ast: @ast::crate,
     ast_map: ast_map::map,
-    exp_map: resolve::exp_map
+    exp_map: resolve::exp_map,
+    deps: [str]
 };
 
 type srv_owner<T> = fn(srv: srv) -> T;
@@ -110,16 +112,31 @@ fn build_ctxt(sess: session::session, ast: @ast::crate,
     let ast = front::test::modify_for_testing(sess, ast);
     let ast_map = ast_map::map_crate(*ast);
     *ignore_errors = true;
+    creader::read_crates(sess, *ast);
     let exp_map = resolve::resolve_crate_reexports(sess, ast_map, ast);
     *ignore_errors = false;
 
     {
         ast: ast,
         ast_map: ast_map,
-        exp_map: exp_map
+        exp_map: exp_map,
+        deps: crate_deps(sess)
     }
 }
 
+// Lists the external crates this crate links against, in the same
+// cstore-numbering order that trans's fill_crate_map walks.
+fn crate_deps(sess: session::session) -> [str] {
+    let deps = [];
+    let cstore = sess.cstore;
+    let i = 1;
+    while cstore::have_crate_data(cstore, i) {
+        deps += [cstore::get_crate_data(cstore, i).name];
+        i += 1;
+    }
+    deps
+}
+
 // FIXME: this whole structure should not be duplicated here. makes it
 // painful to add or remove options.
 fn build_session() -> (session::session, @mutable bool) {
@@ -145,7 +162,9 @@ fn build_session() -> (session::session, @mutable bool) {
         no_trans: false,
         no_asm_comments: false,
         monomorphize: false,
-        warn_unused_imports: false
+        warn_unused_imports: false,
+        personality: none,
+        heap_profile: false
     };
 
     let codemap = codemap::new_codemap();
@@ -4,10 +4,12 @@ type ast_id = int;
 
 type cratedoc = {
     topmod: moddoc,
+    deps: [str],
 };
 
 enum itemtag {
     modtag(moddoc),
+    nmodtag(nmoddoc),
     consttag(constdoc),
     fntag(fndoc),
     enumtag(enumdoc),
@@ -23,6 +25,16 @@ type itemdoc = {
     path: [str],
     brief: option<str>,
     desc: option<str>,
+    // Whether this item is visible outside its enclosing module, per
+    // ast_util::is_exported -- this dialect has no `pub`/`priv` on plain
+    // items, only a module's own `export` view-item list (a module with no
+    // such list exports everything, the same default is_exported itself
+    // uses). See extract::mk_itemdoc. prune_unexported_pass already uses
+    // the same predicate to drop non-exported items from the default doc
+    // pipeline entirely; this field keeps the information around on the
+    // raw tree extract() produces, for any consumer that wants to see
+    // (or otherwise handle) private items rather than have them deleted.
+    vis: bool,
 };
 
 type moddoc = {
@@ -31,6 +43,15 @@ type moddoc = {
     items: ~[itemtag]
 };
 
+type nmoddoc = {
+    item: itemdoc,
+    // The ABI declared on the `native mod` itself, e.g. "cdecl" or
+    // "rust-intrinsic" -- see front::attr::native_abi, which every
+    // native_item_fn in this mod without its own #[abi] inherits from.
+    abi: str,
+    fns: [fndoc]
+};
+
 type constdoc = {
     item: itemdoc,
     ty: option<str>
@@ -38,6 +59,10 @@ type constdoc = {
 
 type fndoc = {
     item: itemdoc,
+    // Type parameter names, e.g. ["T", "U"] for `fn map<T, U>(...)`.
+    // tystr_pass::get_fn_sig separately renders these (with any bounds)
+    // into sig itself; this is for a consumer that wants just the names.
+    tps: [str],
     args: [argdoc],
     return: retdoc,
     failure: option<str>,
@@ -63,7 +88,8 @@ type enumdoc = {
 type variantdoc = {
     name: str,
     desc: option<str>,
-    sig: option<str>
+    sig: option<str>,
+    args: [argdoc]
 };
 
 type resdoc = {
@@ -96,7 +122,8 @@ type impldoc = {
 
 type tydoc = {
     item: itemdoc,
-    sig: option<str>
+    sig: option<str>,
+    ty: option<str>
 };
 
 #[doc = "Some helper methods on moddoc, mostly for testing"]
@@ -111,6 +138,15 @@ impl util for moddoc {
         }
     }
 
+    fn nmods() -> [nmoddoc] {
+        vec::filter_map(*self.items) {|itemtag|
+            alt itemtag {
+              nmodtag(nmoddoc) { some(nmoddoc) }
+              _ { none }
+            }
+        }
+    }
+
     fn fns() -> [fndoc] {
         vec::filter_map(*self.items) {|itemtag|
             alt itemtag {
@@ -183,6 +219,7 @@ impl of item for itemtag {
     fn item() -> itemdoc {
         alt self {
           doc::modtag(doc) { doc.item }
+          doc::nmodtag(doc) { doc.item }
           doc::fntag(doc) { doc.item }
           doc::consttag(doc) { doc.item }
           doc::enumtag(doc) { doc.item }
@@ -198,6 +235,10 @@ impl of item for moddoc {
     fn item() -> itemdoc { self.item }
 }
 
+impl of item for nmoddoc {
+    fn item() -> itemdoc { self.item }
+}
+
 impl of item for fndoc {
     fn item() -> itemdoc { self.item }
 }
@@ -246,4 +287,8 @@ impl util<A:item> for A {
     fn desc() -> option<str> {
         self.item().desc
     }
+
+    fn vis() -> bool {
+        self.item().vis
+    }
 }
\ No newline at end of file
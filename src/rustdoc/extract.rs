@@ -1,6 +1,7 @@
 #[doc = "Converts the Rust AST to the rustdoc document model"];
 
 import rustc::syntax::ast;
+import rustc::front::attr;
 
 export from_srv, extract;
 
@@ -12,16 +13,18 @@ fn from_srv(
     #[doc = "Use the AST service to create a document tree"];
 
     astsrv::exec(srv) {|ctxt|
-        extract(ctxt.ast, default_name)
+        extract(ctxt.ast, default_name, ctxt.deps)
     }
 }
 
 fn extract(
     crate: @ast::crate,
-    default_name: str
+    default_name: str,
+    deps: [str]
 ) -> doc::cratedoc {
     {
         topmod: top_moddoc_from_crate(crate, default_name),
+        deps: deps,
     }
 }
 
@@ -29,20 +32,29 @@ fn top_moddoc_from_crate(
     crate: @ast::crate,
     default_name: str
 ) -> doc::moddoc {
-    moddoc_from_mod(mk_itemdoc(ast::crate_node_id, default_name),
+    // The crate root has no enclosing module to be un-exported from.
+    moddoc_from_mod(mk_itemdoc(ast::crate_node_id, default_name, true),
                     crate.node.module)
 }
 
-fn mk_itemdoc(id: ast::node_id, name: ast::ident) -> doc::itemdoc {
+fn mk_itemdoc(id: ast::node_id, name: ast::ident, vis: bool) -> doc::itemdoc {
     {
         id: id,
         name: name,
         path: [],
         brief: none,
         desc: none,
+        vis: vis,
     }
 }
 
+#[test]
+fn should_mark_exported_items_as_visible() {
+    let doc = test::mk_doc("export a; fn a() { } fn b() { }");
+    assert doc.topmod.fns()[0].vis() == true;
+    assert doc.topmod.fns()[1].vis() == false;
+}
+
 fn moddoc_from_mod(
     itemdoc: doc::itemdoc,
     module: ast::_mod
@@ -50,16 +62,23 @@ fn moddoc_from_mod(
     {
         item: itemdoc,
         items: ~vec::filter_map(module.items) {|item|
-            let itemdoc = mk_itemdoc(item.id, item.ident);
+            let itemdoc = mk_itemdoc(
+                item.id, item.ident,
+                ast_util::is_exported(item.ident, module));
             alt item.node {
               ast::item_mod(m) {
                 some(doc::modtag(
                     moddoc_from_mod(itemdoc, m)
                 ))
               }
-              ast::item_fn(decl, _, _) {
+              ast::item_fn(decl, tps, _) {
                 some(doc::fntag(
-                    fndoc_from_fn(itemdoc, decl)
+                    fndoc_from_fn(itemdoc, decl, tps)
+                ))
+              }
+              ast::item_native_mod(nmod) {
+                some(doc::nmodtag(
+                    nmoddoc_from_native_mod(itemdoc, item.attrs, nmod)
                 ))
               }
               ast::item_const(_, _) {
@@ -102,11 +121,19 @@ fn moddoc_from_mod(
 
 fn fndoc_from_fn(
     itemdoc: doc::itemdoc,
-    decl: ast::fn_decl
+    decl: ast::fn_decl,
+    tps: [ast::ty_param]
 ) -> doc::fndoc {
     {
         item: itemdoc,
+        tps: vec::map(tps) {|tp| tp.ident },
         args: argdocs_from_args(decl.inputs),
+        // Left empty here like every other ty/sig field this module
+        // produces (see argdoc_from_arg above) -- extract's job is only
+        // to shape the raw doc tree from the AST. tystr_pass::merge_ret_ty
+        // is what actually reads decl.output and fills this in later in
+        // the pass pipeline (rustdoc::run), the same way it fills in
+        // argdoc.ty and fndoc.sig.
         return: {
             desc: none,
             ty: none
@@ -116,11 +143,72 @@ fn fndoc_from_fn(
     }
 }
 
+fn nmoddoc_from_native_mod(
+    itemdoc: doc::itemdoc,
+    attrs: [ast::attribute],
+    nmod: ast::native_mod
+) -> doc::nmoddoc {
+    {
+        item: itemdoc,
+        // Defaults to "cdecl" the same way collect_native_item
+        // (trans::base) does when a native mod has no #[abi] of its own.
+        abi: option::from_maybe(
+            "cdecl",
+            attr::get_meta_item_value_str_by_name(attrs, "abi")),
+        fns: vec::map(nmod.items) {|item|
+            nativefndoc_from_native_item(item, itemdoc.vis)
+        }
+    }
+}
+
+fn nativefndoc_from_native_item(
+    item: @ast::native_item,
+    // A native fn isn't independently exportable in this dialect -- only
+    // the enclosing `native mod` item is named in an `export` list -- so it
+    // inherits its whole mod's visibility.
+    vis: bool
+) -> doc::fndoc {
+    alt item.node {
+      ast::native_item_fn(decl, tps) {
+        fndoc_from_fn(
+            mk_itemdoc(item.id, item.ident, vis),
+            decl, tps)
+      }
+    }
+}
+
+#[test]
+fn should_extract_native_fn_name_and_args() {
+    let source = "native mod a { fn b(c: int); }";
+    let ast = parse::from_str(source);
+    let doc = extract(ast, "", []);
+    let nmod = doc.topmod.nmods()[0];
+    assert nmod.fns[0].name() == "b";
+    assert nmod.fns[0].args[0].name == "c";
+}
+
+#[test]
+fn should_extract_native_mod_abi() {
+    let source = "#[abi = \"cdecl\"] native mod a { fn b(); }";
+    let ast = parse::from_str(source);
+    let doc = extract(ast, "", []);
+    assert doc.topmod.nmods()[0].abi == "cdecl";
+}
+
+#[test]
+fn should_extract_fn_tps() {
+    let source = "fn f<T>() { }";
+    let ast = parse::from_str(source);
+    let doc = extract(ast, "", []);
+    let fn_ = doc.topmod.fns()[0];
+    assert fn_.tps == ["T"];
+}
+
 #[test]
 fn should_extract_fn_args() {
     let source = "fn a(b: int, c: int) { }";
     let ast = parse::from_str(source);
-    let doc = extract(ast, "");
+    let doc = extract(ast, "", []);
     let fn_ = doc.topmod.fns()[0];
     assert fn_.args[0].name == "b";
     assert fn_.args[1].name == "c";
@@ -134,6 +222,9 @@ fn argdoc_from_arg(arg: ast::arg) -> doc::argdoc {
     {
         name: arg.ident,
         desc: none,
+        // As with fndoc's return.ty (see fndoc_from_fn above),
+        // tystr_pass::decl_arg_tys fills this in later in the pass
+        // pipeline rather than here.
         ty: none
     }
 }
@@ -141,6 +232,7 @@ fn argdoc_from_arg(arg: ast::arg) -> doc::argdoc {
 fn constdoc_from_const(itemdoc: doc::itemdoc) -> doc::constdoc {
     {
         item: itemdoc,
+        // tystr_pass::fold_const fills this in with pprust::ty_to_str.
         ty: none
     }
 }
@@ -172,10 +264,36 @@ fn variantdoc_from_variant(variant: ast::variant) -> doc::variantdoc {
     {
         name: variant.node.name,
         desc: none,
-        sig: none
+        // tystr_pass::fold_enum fills this in with pprust::variant_to_str.
+        sig: none,
+        args: variantargdocs_from_variant_args(variant.node.args)
     }
 }
 
+fn variantargdocs_from_variant_args(
+    args: [ast::variant_arg]
+) -> [doc::argdoc] {
+    vec::map(args, variantargdoc_from_variant_arg)
+}
+
+fn variantargdoc_from_variant_arg(arg: ast::variant_arg) -> doc::argdoc {
+    // Unlike a fn/method/resource arg, a tuple-like variant's args are
+    // positional (`ast::variant_arg` carries a `ty` and `id`, no `ident`),
+    // so there's no source name to put here.
+    {
+        name: "",
+        desc: none,
+        // tystr_pass::fold_enum fills this in with pprust::ty_to_str.
+        ty: none
+    }
+}
+
+#[test]
+fn should_extract_enum_variant_args() {
+    let doc = test::mk_doc("enum e { v(int) }");
+    assert vec::len(doc.topmod.enums()[0].variants[0].args) == 1u;
+}
+
 #[test]
 fn should_extract_enums() {
     let doc = test::mk_doc("enum e { v }");
@@ -196,6 +314,7 @@ fn resdoc_from_resource(
     {
         item: itemdoc,
         args: argdocs_from_args(decl.inputs),
+        // tystr_pass::fold_res fills this in with pprust::res_to_str.
         sig: none
     }
 }
@@ -213,6 +332,18 @@ fn should_extract_resource_args() {
     assert doc.topmod.resources()[0].args[0].name == "b";
 }
 
+// `methods` here is `[ast::ty_method]` (ast.rs: `item_iface([ty_param],
+// [ty_method])`), and `ty_method` is signature-only -- ident/attrs/decl,
+// no body field at all. Distinguishing "required" from "provided
+// (defaulted)" iface methods, the way this doc comment's request would
+// like, needs a body an iface method can actually carry, which isn't
+// something ifacedoc_from_iface or the doc model can retrofit on its
+// own: parse_item_iface (syntax::parse::parser) calls parse_ty_methods,
+// the same signature-only parser used for native mod fn decls, with no
+// brace-delimited body ever consumed for an iface method. Adding that
+// would be a parser/AST grammar change (a new method-or-ty_method sum
+// type, threaded through typeck's iface handling and trans's vtable
+// building for the default-body case), not a rustdoc-local one.
 fn ifacedoc_from_iface(
     itemdoc: doc::itemdoc,
     methods: [ast::ty_method]
@@ -230,6 +361,9 @@ fn ifacedoc_from_iface(
                     ty: none
                 },
                 failure: none,
+                // tystr_pass::merge_methods fills in return.ty and sig
+                // (via get_method_ret_ty/get_method_sig) for both iface
+                // and impl methods.
                 sig: none
             }
         }
@@ -260,6 +394,8 @@ fn impldoc_from_impl(
 ) -> doc::impldoc {
     {
         item: itemdoc,
+        // tystr_pass::fold_impl fills both of these in from the
+        // item_impl's iface_ty/self_ty AST nodes via pprust::ty_to_str.
         iface_ty: none,
         self_ty: none,
         methods: vec::map(methods) {|method|
@@ -308,7 +444,11 @@ fn tydoc_from_ty(
 ) -> doc::tydoc {
     {
         item: itemdoc,
-        sig: none
+        // tystr_pass::fold_type fills this in as "type <name><params> = <ty>".
+        sig: none,
+        // tystr_pass::fold_type fills this in with pprust::ty_to_str of
+        // just the aliased type, e.g. "int" for "type t = int".
+        ty: none
     }
 }
 
@@ -323,7 +463,7 @@ mod test {
 
     fn mk_doc(source: str) -> doc::cratedoc {
         let ast = parse::from_str(source);
-        extract(ast, "")
+        extract(ast, "", [])
     }
 
     #[test]
@@ -372,7 +512,7 @@ mod test {
     fn extract_should_use_default_crate_name() {
         let source = "";
         let ast = parse::from_str(source);
-        let doc = extract(ast, "burp");
+        let doc = extract(ast, "burp", []);
         assert doc.topmod.name() == "burp";
     }
 
@@ -384,4 +524,13 @@ mod test {
             assert doc.topmod.name() == "name";
         }
     }
+
+    #[test]
+    fn extract_should_list_used_crates_as_deps() {
+        let source = "use std;";
+        astsrv::from_str(source) {|srv|
+            let doc = from_srv(srv, "name");
+            assert vec::contains(doc.deps, "std");
+        }
+    }
 }
\ No newline at end of file
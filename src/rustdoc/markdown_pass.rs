@@ -123,6 +123,24 @@ fn should_write_full_path_to_mod() {
     assert str::contains(markdown, "# Module `a::b::c`");
 }
 
+fn write_nmod(
+    ctxt: ctxt,
+    nmoddoc: doc::nmoddoc
+) {
+    write_header(ctxt, h1, #fmt("Native module `%s`", nmoddoc.name()));
+    write_brief(ctxt, nmoddoc.brief());
+    write_desc(ctxt, nmoddoc.desc());
+    for fndoc in nmoddoc.fns {
+        write_fn(ctxt, fndoc);
+    }
+}
+
+#[test]
+fn should_write_native_mod_header() {
+    let markdown = test::render("native mod a { fn b(); }");
+    assert str::contains(markdown, "# Native module `a`");
+}
+
 fn write_mod_contents(
     ctxt: ctxt,
     doc: doc::moddoc
@@ -133,6 +151,7 @@ fn write_mod_contents(
     for itemtag in *doc.items {
         alt itemtag {
           doc::modtag(moddoc) { write_mod(ctxt, moddoc) }
+          doc::nmodtag(nmoddoc) { write_nmod(ctxt, nmoddoc) }
           doc::fntag(fndoc) { write_fn(ctxt, fndoc) }
           doc::consttag(constdoc) { write_const(ctxt, constdoc) }
           doc::enumtag(enumdoc) { write_enum(ctxt, enumdoc) }
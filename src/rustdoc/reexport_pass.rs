@@ -219,6 +219,12 @@ fn merge_reexports(
                 with doc
             })
           }
+          doc::nmodtag(doc @ {item, _}) {
+            doc::nmodtag({
+                item: rename(item, name)
+                with doc
+            })
+          }
           doc::consttag(doc @ {item, _}) {
             doc::consttag({
                 item: rename(item, name)
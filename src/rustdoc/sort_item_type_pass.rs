@@ -14,6 +14,7 @@ fn mk_pass() -> pass {
               doc::impltag(_) { 5 }
               doc::fntag(_) { 6 }
               doc::modtag(_) { 7 }
+              doc::nmodtag(_) { 8 }
               _ { fail }
             }
         }
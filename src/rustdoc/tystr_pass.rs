@@ -48,9 +48,15 @@ fn get_fn_sig(srv: astsrv::srv, fn_id: doc::ast_id) -> option<str> {
         alt check ctxt.ast_map.get(fn_id) {
           ast_map::node_item(@{
             ident: ident,
-            node: ast::item_fn(decl, _, blk), _
+            node: ast::item_fn(decl, tps, blk), _
           }, _) {
-            some(pprust::fun_to_str(decl, ident, []))
+            some(pprust::fun_to_str(decl, ident, tps))
+          }
+          ast_map::node_native_item(@{
+            ident: ident,
+            node: ast::native_item_fn(decl, tps), _
+          }, _) {
+            some(pprust::fun_to_str(decl, ident, tps))
           }
         }
     }
@@ -62,6 +68,12 @@ fn should_add_fn_sig() {
     assert doc.topmod.fns()[0].sig == some("fn a() -> int");
 }
 
+#[test]
+fn should_add_fn_sig_generics() {
+    let doc = test::mk_doc("fn a<T>() -> int { }");
+    assert doc.topmod.fns()[0].sig == some("fn a<T>() -> int");
+}
+
 fn merge_ret_ty(
     srv: astsrv::srv,
     fn_id: doc::ast_id,
@@ -83,6 +95,9 @@ fn get_ret_ty(srv: astsrv::srv, fn_id: doc::ast_id) -> option<str> {
         alt check ctxt.ast_map.get(fn_id) {
           ast_map::node_item(@{
             node: ast::item_fn(decl, _, _), _
+          }, _) |
+          ast_map::node_native_item(@{
+            node: ast::native_item_fn(decl, _), _
           }, _) {
             ret_ty_to_str(decl)
           }
@@ -111,6 +126,12 @@ fn should_not_add_nil_ret_type() {
     assert doc.topmod.fns()[0].return.ty == none;
 }
 
+#[test]
+fn should_add_fn_ret_type_with_body() {
+    let doc = test::mk_doc("fn a() -> int { 0 }");
+    assert doc.topmod.fns()[0].return.ty == some("int");
+}
+
 fn merge_arg_tys(
     srv: astsrv::srv,
     fn_id: doc::ast_id,
@@ -135,6 +156,9 @@ fn get_arg_tys(srv: astsrv::srv, fn_id: doc::ast_id) -> [(str, str)] {
           }, _) |
           ast_map::node_item(@{
             node: ast::item_res(decl, _, _, _, _), _
+          }, _) |
+          ast_map::node_native_item(@{
+            node: ast::native_item_fn(decl, _), _
           }, _) {
             decl_arg_tys(decl)
           }
@@ -144,10 +168,18 @@ fn get_arg_tys(srv: astsrv::srv, fn_id: doc::ast_id) -> [(str, str)] {
 
 fn decl_arg_tys(decl: ast::fn_decl) -> [(str, str)] {
     vec::map(decl.inputs) {|arg|
-        (arg.ident, pprust::ty_to_str(arg.ty))
+        (arg.ident, arg_ty_to_str(arg))
     }
 }
 
+// Prefixes the mode sigil (&, -, &&, +, ++; see pprust::mode_to_str) the
+// same way the syntax printer does for a real fn signature, so e.g. a
+// `&&x: T` (by_ref) argument doesn't render identically to a plain `x: T`
+// (by_val) one in generated docs.
+fn arg_ty_to_str(arg: ast::arg) -> str {
+    pprust::mode_to_str(arg.mode) + pprust::ty_to_str(arg.ty)
+}
+
 #[test]
 fn should_add_arg_types() {
     let doc = test::mk_doc("fn a(b: int, c: bool) { }");
@@ -156,6 +188,13 @@ fn should_add_arg_types() {
     assert fn_.args[1].ty == some("bool");
 }
 
+#[test]
+fn should_render_arg_mode_in_arg_type() {
+    let doc = test::mk_doc("fn a(&&b: int) { }");
+    let fn_ = doc.topmod.fns()[0];
+    assert fn_.args[0].ty == some("&&int");
+}
+
 fn fold_const(
     fold: fold::fold<astsrv::srv>,
     doc: doc::constdoc
@@ -182,6 +221,12 @@ fn should_add_const_types() {
     assert doc.topmod.consts()[0].ty == some("bool");
 }
 
+#[test]
+fn should_add_const_types_for_int_literals() {
+    let doc = test::mk_doc("const a: int = 0;");
+    assert doc.topmod.consts()[0].ty == some("int");
+}
+
 fn fold_enum(
     fold: fold::fold<astsrv::srv>,
     doc: doc::enumdoc
@@ -190,7 +235,7 @@ fn fold_enum(
 
     {
         variants: vec::map(doc.variants) {|variant|
-            let sig = astsrv::exec(srv) {|ctxt|
+            let (sig, arg_tys) = astsrv::exec(srv) {|ctxt|
                 alt check ctxt.ast_map.get(doc.id()) {
                   ast_map::node_item(@{
                     node: ast::item_enum(ast_variants, _), _
@@ -200,13 +245,19 @@ fn fold_enum(
                             v.node.name == variant.name
                         });
 
-                    pprust::variant_to_str(ast_variant)
+                    (pprust::variant_to_str(ast_variant),
+                     vec::map(ast_variant.node.args) {|arg|
+                         pprust::ty_to_str(arg.ty)
+                     })
                   }
                 }
             };
 
             {
-                sig: some(sig)
+                sig: some(sig),
+                args: vec::map2(variant.args, arg_tys) {|arg, ty|
+                    { ty: some(ty) with arg }
+                }
                 with variant
             }
         }
@@ -220,6 +271,12 @@ fn should_add_variant_sigs() {
     assert doc.topmod.enums()[0].variants[0].sig == some("b(int)");
 }
 
+#[test]
+fn should_add_variant_arg_types() {
+    let doc = test::mk_doc("enum a { b(int) }");
+    assert doc.topmod.enums()[0].variants[0].args[0].ty == some("int");
+}
+
 fn fold_res(
     fold: fold::fold<astsrv::srv>,
     doc: doc::resdoc
@@ -231,9 +288,9 @@ fn fold_res(
         sig: some(astsrv::exec(srv) {|ctxt|
             alt check ctxt.ast_map.get(doc.id()) {
               ast_map::node_item(@{
-                node: ast::item_res(decl, _, _, _, _), _
+                node: ast::item_res(decl, tps, _, _, _), _
               }, _) {
-                pprust::res_to_str(decl, doc.name(), [])
+                pprust::res_to_str(decl, doc.name(), tps)
               }
             }
         })
@@ -351,7 +408,8 @@ fn get_method_sig(
                 method.ident == method_name
             } {
                 some(method) {
-                    some(pprust::fun_to_str(method.decl, method.ident, []))
+                    some(pprust::fun_to_str(
+                        method.decl, method.ident, method.tps))
                 }
             }
           }
@@ -362,7 +420,8 @@ fn get_method_sig(
                 method.ident == method_name
             } {
                 some(method) {
-                    some(pprust::fun_to_str(method.decl, method.ident, []))
+                    some(pprust::fun_to_str(
+                        method.decl, method.ident, method.tps))
                 }
             }
           }
@@ -428,6 +487,12 @@ fn should_add_iface_method_sigs() {
     assert doc.topmod.ifaces()[0].methods[0].sig == some("fn a() -> int");
 }
 
+#[test]
+fn should_add_iface_method_sig_generics() {
+    let doc = test::mk_doc("iface i { fn a<T>() -> int; }");
+    assert doc.topmod.ifaces()[0].methods[0].sig == some("fn a<T>() -> int");
+}
+
 #[test]
 fn should_add_iface_method_ret_types() {
     let doc = test::mk_doc("iface i { fn a() -> int; }");
@@ -528,23 +593,26 @@ fn fold_type(
 
     let srv = fold.ctxt;
 
-    {
-        sig: astsrv::exec(srv) {|ctxt|
-            alt ctxt.ast_map.get(doc.id()) {
-              ast_map::node_item(@{
-                ident: ident,
-                node: ast::item_ty(ty, params), _
-              }, _) {
-                some(#fmt(
-                    "type %s%s = %s",
-                    ident,
-                    pprust::typarams_to_str(params),
-                    pprust::ty_to_str(ty)
-                ))
-              }
-              _ { fail "expected type" }
-            }
+    let (sig, ty) = astsrv::exec(srv) {|ctxt|
+        alt ctxt.ast_map.get(doc.id()) {
+          ast_map::node_item(@{
+            ident: ident,
+            node: ast::item_ty(ty, params), _
+          }, _) {
+            (some(#fmt(
+                "type %s%s = %s",
+                ident,
+                pprust::typarams_to_str(params),
+                pprust::ty_to_str(ty)
+            )), some(pprust::ty_to_str(ty)))
+          }
+          _ { fail "expected type" }
         }
+    };
+
+    {
+        sig: sig,
+        ty: ty
         with doc
     }
 }
@@ -555,6 +623,12 @@ fn should_add_type_signatures() {
     assert doc.topmod.types()[0].sig == some("type t<T> = int");
 }
 
+#[test]
+fn should_add_type_target_types() {
+    let doc = test::mk_doc("type t = int;");
+    assert doc.topmod.types()[0].ty == some("int");
+}
+
 #[cfg(test)]
 mod test {
     fn mk_doc(source: str) -> doc::cratedoc {
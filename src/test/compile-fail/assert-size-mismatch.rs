@@ -0,0 +1,8 @@
+// error-pattern:has size
+
+type four_bytes = {a: u8, b: u8, c: u8, d: u8};
+
+#[assert_size = 8]
+type wrong_size = four_bytes;
+
+fn main() { }
@@ -0,0 +1,5 @@
+// error-pattern:string literal
+
+fn main() {
+    let s = #concat[1];
+}
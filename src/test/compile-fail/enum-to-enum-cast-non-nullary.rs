@@ -0,0 +1,9 @@
+//error-pattern: cannot cast between enums that have variants carrying data
+
+enum a { a_nullary, a_other(int), }
+enum b { b_nullary, b_other(int), }
+
+fn main() {
+    let v = a_nullary;
+    let w = v as b;
+}
@@ -0,0 +1,17 @@
+// #[ifunc] is meant to emit a function as a GNU indirect function, so the
+// dynamic linker calls it once and installs the returned function pointer
+// as the resolved symbol (see set_ifunc in trans/base.rs), but the LLVM C
+// API bound in this tree has no ifunc (GlobalIFunc) construct to emit --
+// LLVMAddAlias only produces an ordinary alias. There's nothing this
+// harness can assert an ifunc was emitted with ifunc semantics, since none
+// ever is; the reachable, host-independent behavior to test instead is
+// that the attribute is rejected with span_unimpl rather than silently
+// ignored.
+#[ifunc]
+fn resolver() -> fn@() -> int { //! ERROR this LLVM binding has no ifunc
+    ret fallback;
+}
+
+fn fallback() -> int { 0 }
+
+fn main() { }
@@ -0,0 +1,5 @@
+// error-pattern:couldn't read
+
+fn main() {
+    let s = #include_str["this-file-does-not-exist.txt"];
+}
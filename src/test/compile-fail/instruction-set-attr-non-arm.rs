@@ -0,0 +1,16 @@
+// #[instruction_set(arm)]/#[instruction_set(thumb)] is meant to override
+// the module-wide ARM/Thumb choice per function (see
+// set_instruction_set in trans/base.rs), but the LLVM C API bound in
+// this tree has no target-dependent function attribute to actually carry
+// that override, so even on an arm target this can only report
+// span_unimpl, not emit anything. There's no per-test target directive
+// in compiletest (xfail-test only keys off the host OS), so an
+// "ARM-targeted test that the annotated function carries the
+// instruction-set attribute" isn't something this harness can express;
+// the reachable, host-independent behavior to test instead is that using
+// the attribute on the compiletest suite's default (non-arm) target is
+// rejected up front.
+#[instruction_set(arm)]
+fn handler() { } //! ERROR is only supported on arm targets
+
+fn main() { }
@@ -0,0 +1,7 @@
+// item_section requires #[section = "..."] to be a plain string, since
+// that's the only form LLVMSetSection can use; a bare #[section] doesn't
+// say what section to place the global in.
+#[section]
+const sectioned: int = 41; //! ERROR section must be a string
+
+fn main() { }
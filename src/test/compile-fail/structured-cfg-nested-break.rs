@@ -0,0 +1,19 @@
+// compile-flags:--structured-cfg
+// error-pattern: nested inside an if/loop
+
+// trans_break_cont now rejects, under --structured-cfg, a break/continue
+// that unwinds past its immediate loop-body scope: doing so branches
+// straight from deep inside an if/loop's nesting to an ancestor's
+// continue/exit block, a cross-edge this tree has no relooper to rebuild
+// as properly nested wasm-style control flow (see the doc comment on
+// trans_break_cont in trans/base.rs). Here the `break` is nested inside
+// an `if` inside the `while`, so it should be rejected.
+fn main() {
+    let mutable i = 0;
+    while i < 10 {
+        if i == 5 {
+            break;
+        }
+        i += 1;
+    }
+}
@@ -0,0 +1,14 @@
+// error-pattern: expected `int` but found `'a1`
+
+// ty_to_str's ty_param arm used to compute a letter as
+// ('a' as u8) + (id as u8), which runs off the end of the lowercase
+// alphabet into punctuation ('{', '|', ...) once a function has more than
+// 26 type parameters. It now wraps with a numeric suffix instead ('a,
+// ..., 'z, 'a1, 'b1, ...), so the 27th parameter here (id 26) prints as
+// 'a1, a legal-looking name, in the mismatched-types error below.
+fn f<A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X,
+     Y, Z, AA, AB, AC>(x: AA) -> int {
+    x
+}
+
+fn main() { }
@@ -0,0 +1,6 @@
+// error-pattern:x must be positive
+
+fn main() {
+    let x = -1;
+    assert x > 0, "x must be positive";
+}
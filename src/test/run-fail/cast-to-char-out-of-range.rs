@@ -0,0 +1,7 @@
+// error-pattern:cast to char out of range
+
+fn main() {
+    let i = 0x110000;
+    let c = i as char;
+    log(error, c);
+}
@@ -0,0 +1,29 @@
+// A dispatch table with enough string-literal arms to trigger the
+// length-bucketed switch lowering in trans::alt::compile_str_switch.
+//
+// Note: this exercises an `alt`/`match` on string literals, not the
+// const `[(str, fn)]`-array-to-perfect-hash-jump-table shape asked for
+// in the original request -- see the comment above compile_str_switch
+// for why that recognizer isn't implemented here.
+fn handle(key: str) -> int {
+    alt check key {
+      "add" { 1 }
+      "sub" { 2 }
+      "mul" { 3 }
+      "div" { 4 }
+      "neg" { 5 }
+      _ { 0 }
+    }
+}
+
+fn main() {
+    assert handle("add") == 1;
+    assert handle("sub") == 2;
+    assert handle("mul") == 3;
+    assert handle("div") == 4;
+    assert handle("neg") == 5;
+    assert handle("nope") == 0;
+    // Same length as "add"/"sub"/... but not a listed key: exercises the
+    // length-bucket-matches-but-string-differs path.
+    assert handle("xyz") == 0;
+}
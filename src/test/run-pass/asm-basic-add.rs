@@ -0,0 +1,17 @@
+// The #asm syntax extension lowers to ast::expr_asm and trans_asm emits it
+// as an LLVM inline-asm call. Integer operands only, for now. This exercises
+// a simple two-instruction template with both an output and two inputs, and
+// a clobber list, on x86: `movl` the first input into the output register,
+// then `addl` the second input into it (clobbering the flags register).
+
+fn main() {
+    let a: int = 4;
+    let b: int = 5;
+    let mutable c: int = 0;
+    #asm["movl $1, $0; addl $2, $0",
+         [("=r", c)],
+         [("r", a), ("r", b)],
+         ["cc"],
+         false];
+    assert c == 9;
+}
@@ -0,0 +1,9 @@
+type four_bytes = {a: u8, b: u8, c: u8, d: u8};
+
+#[assert_size = 4]
+type right_size = four_bytes;
+
+fn main() {
+    let x: right_size = {a: 1u8, b: 2u8, c: 3u8, d: 4u8};
+    assert x.d == 4u8;
+}
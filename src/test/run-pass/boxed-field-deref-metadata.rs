@@ -0,0 +1,19 @@
+// load_if_immediate tags the load of a box/unique-typed field with
+// !align now (set_box_pointer_metadata in trans/base.rs carries the
+// logic), and with !dereferenceable too when the pointee's size is
+// statically known, so autoderef's following load through the pointer
+// doesn't make LLVM re-derive either fact on its own. Metadata like
+// this is advisory to the optimizer, not something a running program
+// observes directly -- so the failure worth testing for is a wrong
+// claim, not a missing one: an !align or !dereferenceable annotation
+// that overstates reality licenses LLVM to miscompile the load
+// downstream. This exercises exactly the shape the metadata attaches
+// to, a boxed field loaded and dereferenced through a record, so a
+// wrong annotation would surface as a bad value or a crash, not
+// silence.
+type has_box = {x: @int};
+
+fn main() {
+    let r: has_box = {x: @42};
+    assert *r.x == 42;
+}
@@ -0,0 +1,19 @@
+// invoke_ now emits a plain call (rather than an invoke + landing pad)
+// when no enclosing scope has cleanups to run; exercise a call in a scope
+// with no resources/cleanups live, and a nested call in a scope that does
+// have one, so both codegen paths run and return the right values.
+fn add(a: int, b: int) -> int { a + b }
+
+resource r(i: @mutable int) { *i += 1; }
+
+fn with_cleanup(i: @mutable int) -> int {
+    let _res = r(i);
+    add(1, 2)
+}
+
+fn main() {
+    assert add(1, 2) == 3;
+    let count = @mutable 0;
+    assert with_cleanup(count) == 3;
+    assert *count == 1;
+}
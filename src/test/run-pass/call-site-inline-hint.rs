@@ -0,0 +1,28 @@
+// trans_call runs a small AST-size cost model now --
+// inline_decision_for_call, in trans::base -- on direct calls to a
+// local, non-generic function with no #[inline...] attribute of its
+// own, and hints AlwaysInline or NoInline directly on that Call/Invoke
+// instruction (set_call_always_inline/set_call_no_inline) rather than on
+// the callee's definition, so the same small function can be inlined at
+// one call site and left alone at another. That hint only advises LLVM,
+// so getting it wrong wouldn't change this test's output either way.
+// What's worth guarding instead is that inline_decision_for_call's
+// callee-size check runs safely on a function that already carries its
+// own explicit #[inline(never)] (`explicit`), without double-applying
+// or fighting that attribute, alongside the unattributed small callee
+// (`tiny`) the cost model is actually meant to act on.
+fn tiny(a: int, b: int) -> int { ret a + b; }
+
+#[inline(never)]
+fn explicit(a: int, b: int) -> int { ret a + b; }
+
+fn main() {
+    let mutable sum = 0;
+    let mutable i = 0;
+    while i < 10 {
+        sum += tiny(i, 1);
+        i += 1;
+    }
+    assert sum == tiny(0, 45);
+    assert explicit(2, 3) == 5;
+}
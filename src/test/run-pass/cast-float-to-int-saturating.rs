@@ -0,0 +1,33 @@
+use std;
+import float;
+
+fn main() {
+    let inf = float::infinity;
+    let nan = float::NaN;
+
+    assert (inf as i32) == 2147483647;
+    assert (-inf as i32) == -2147483648;
+    assert (nan as i32) == 0;
+    assert (1.0 as i32) == 1;
+
+    assert (inf as u8) == 255u8;
+    assert (-inf as u8) == 0u8;
+    assert (nan as u8) == 0u8;
+    assert (42.0 as u8) == 42u8;
+
+    // i64::max_value (2^63-1) isn't exactly representable in f64, so it
+    // rounds up to 9223372036854775808.0 (2^63) when written as a float
+    // literal. That's still an ordinary finite value, and it's already
+    // out of i64's range -- it must saturate to i64::max_value, not fall
+    // through to a raw, LLVM-undefined FPToSI.
+    assert (9223372036854775808.0f64 as i64) == 9223372036854775807i64;
+    assert (-9223372036854775808.0f64 as i64) == -9223372036854775808i64;
+    assert (inf as i64) == 9223372036854775807i64;
+    assert (-inf as i64) == -9223372036854775808i64;
+
+    // Same rounding issue on the unsigned side: u64::max_value
+    // (2^64-1) rounds up to 2^64 as a float literal.
+    assert (18446744073709551616.0f64 as u64) == 18446744073709551615u64;
+    assert (inf as u64) == 18446744073709551615u64;
+    assert (-inf as u64) == 0u64;
+}
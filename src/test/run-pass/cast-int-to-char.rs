@@ -0,0 +1,6 @@
+fn main() {
+    let c = 65 as char;
+    assert c == 'A';
+    let i = 'a' as int;
+    assert i == 97;
+}
@@ -0,0 +1,14 @@
+// sys::clflush wraps the rusti::clflush rust-intrinsic. Its runtime body,
+// rust_intrinsic_clflush in src/rt/intrinsics/intrinsics.cpp, emits the
+// x86 clflush instruction through inline asm and is a no-op on other
+// architectures. clflush's whole effect is evicting a cache line -- it
+// changes nothing architecturally visible about the value at that
+// address -- so no assertion here could ever tell "the instruction ran"
+// apart from "the call was silently dropped". What's left to check is
+// the more basic guarantee: the intrinsic is callable with a real
+// pointer and leaves the memory behind it intact.
+fn main() {
+    let x = 42u8;
+    sys::clflush(ptr::addr_of(x));
+    assert x == 42u8;
+}
@@ -0,0 +1,21 @@
+// emit_tydescs used to unconditionally null out a tydesc's cmp_glue
+// slot. lazily_emit_cmp_glue now fills it in on first use instead --
+// with the crate's one shared cmp glue trampoline from make_cmp_glue,
+// reached through call_cmp_glue -- and leaves it null for anything
+// that's never compared. This can't read back which tydescs ended up
+// with a null slot; a linker or IR view would be needed for that. Two
+// things it can check would fail if the lazy path wired the glue up
+// wrong: `compared` gets correct answers for both an equal and an
+// unequal comparison, which rules out a glue pointer that's present but
+// aimed at the wrong comparator, and `moved` -- a same-shaped type
+// that's only ever moved -- is left alone by the lazy path entirely.
+fn only_moved<T>(x: T) -> T { ret x; }
+
+fn main() {
+    let compared = {a: 1, b: 2};
+    assert compared == {a: 1, b: 2};
+    assert compared != {a: 1, b: 3};
+
+    let moved = only_moved({a: 1, b: 2});
+    assert moved.a == 1;
+}
@@ -0,0 +1,15 @@
+// compile-flags:--code-model kernel
+
+// trans_crate threads sess.opts.code_model through to
+// LLVMRustWriteOutputFile now, defaulting to "small" when nothing
+// overrides it. Reading which code model actually made it into the
+// object file is an objdump/readelf question, not one a running program
+// answers. There's a coarser failure this flag can still trigger,
+// though: an unrecognized or mismatched CodeModel value reaching LLVM's
+// target machine construction fails the whole compile outright, rather
+// than quietly producing a wrong-but-working binary. So "this still
+// compiles and runs under --code-model kernel" is a real check, just a
+// blunt one.
+fn main() {
+    assert 1 + 1 == 2;
+}
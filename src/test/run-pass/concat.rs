@@ -0,0 +1,7 @@
+use std;
+import str;
+
+fn main() {
+    assert str::eq(#concat["foo", "bar"], "foobar");
+    assert str::eq(#concat["foo", "bar", "baz"], "foobarbaz");
+}
@@ -0,0 +1,21 @@
+// compile-flags:--xg
+
+// trans_item's item_enum arm now emits DWARF enum metadata via
+// debuginfo::create_enum_metadata: an enumeration type (one enumerator per
+// variant) for a C-like enum, and a discriminant-only struct for a tagged
+// one. This just exercises both shapes without inspecting the emitted
+// metadata.
+enum suit { clubs, diamonds, hearts, spades, }
+
+enum card { number(suit, int), joker, }
+
+fn main() {
+    let s = hearts;
+    assert s == hearts;
+
+    let c = number(spades, 7);
+    alt c {
+      number(suit, n) { assert suit == spades && n == 7; }
+      joker { fail; }
+    }
+}
@@ -0,0 +1,10 @@
+// compile-flags:--xg
+
+// Locals that are kept as immediate SSA values (rather than given a
+// stack slot) have no address for llvm.dbg.declare to point at; trans
+// falls back to llvm.dbg.value for them. This just exercises that path
+// without inspecting the emitted debug info.
+fn main() {
+    let x = 1 + 2;
+    assert x == 3;
+}
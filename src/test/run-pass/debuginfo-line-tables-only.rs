@@ -0,0 +1,16 @@
+// compile-flags:-g
+
+// -g alone now means level 1 ("line tables only"): update_source_pos
+// still runs, giving accurate line numbers for backtraces, but
+// create_local_var/create_arg/create_function are skipped, so no
+// llvm.dbg.declare/llvm.dbg.value get emitted for locals or args (that
+// needs the fuller --xg level). This just exercises the level-1 path
+// without inspecting the emitted debug info.
+fn add(a: int, b: int) -> int {
+    let sum = a + b;
+    ret sum;
+}
+
+fn main() {
+    assert add(2, 3) == 5;
+}
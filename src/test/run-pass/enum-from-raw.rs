@@ -0,0 +1,23 @@
+// Round-trips an enum value through discriminant_value + a raw copy of its
+// payload bytes + enum_from_raw, exercising the rust_intrinsic_enum_from_raw
+// runtime intrinsic (src/rt/intrinsics/intrinsics.cpp) against the
+// T_opaque_enum layout (tag word followed by the variant payload) that
+// middle::trans::common actually uses.
+
+enum e { a(int), b, }
+
+type raw_e = {tag: int, val: int};
+
+fn main() unsafe {
+    let x = a(42);
+    let discr = unsafe::discriminant_value(x);
+
+    let raw: raw_e = unsafe::reinterpret_cast(x);
+    let data: *u8 = unsafe::reinterpret_cast(ptr::addr_of(raw.val));
+
+    let y: e = unsafe::enum_from_raw(discr, data);
+    alt y {
+      a(v) { assert v == 42; }
+      b { fail "enum_from_raw produced the wrong variant"; }
+    }
+}
@@ -0,0 +1,9 @@
+enum color { red = 1, green = 2, blue = 3, }
+enum signal { go = 1, caution = 2, stop = 3, }
+
+fn main() {
+    let c = green;
+    let s = c as signal;
+    assert s as int == 2;
+    assert (stop as color) as int == 3;
+}
@@ -0,0 +1,24 @@
+// #[abi = "fastcall"]/#[abi = "stdcall"] on an ordinary fn now flows a
+// lib::llvm::CallConv through register_fn_fuller -- attr::find_fn_call_conv
+// does the reading -- recorded per-fn in crate_ctxt.item_ccs, and
+// lval_static_fn reads that back so every direct call site applies the
+// same convention through invoke_cc's CallWithConv/InvokeWithConv. On
+// x86-64 both fastcall and stdcall collapse to the platform's ordinary
+// calling convention, so there's no register-shuffling difference for
+// this test to observe even given IR access. What actually matters is
+// whether item_ccs's writer and reader agree with each other: if
+// register_fn_fuller's write and lval_static_fn's read ever disagreed on
+// the recorded convention, calling these functions would corrupt the
+// stack or arguments outright rather than just return a
+// plausible-looking wrong value, which is the failure these two calls
+// are positioned to catch.
+#[abi = "fastcall"]
+fn add_fastcall(x: int, y: int) -> int { ret x + y; }
+
+#[abi = "stdcall"]
+fn add_stdcall(x: int, y: int) -> int { ret x + y; }
+
+fn main() {
+    assert add_fastcall(1, 2) == 3;
+    assert add_stdcall(3, 4) == 7;
+}
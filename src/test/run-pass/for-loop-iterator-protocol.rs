@@ -0,0 +1,23 @@
+// trans_for now lowers `for pat in seq { ... }` over any type with a
+// `next() -> option<T>` method, not just vectors and strings.
+
+type range = @{mutable cur: int, stop: int};
+
+impl range_iter for range {
+    fn next() -> option<int> {
+        if self.cur >= self.stop {
+            none
+        } else {
+            let v = self.cur;
+            self.cur += 1;
+            some(v)
+        }
+    }
+}
+
+fn main() {
+    let r: range = @{mutable cur: 0, stop: 5};
+    let seen = [];
+    for i in r { seen += [i]; }
+    assert seen == [0, 1, 2, 3, 4];
+}
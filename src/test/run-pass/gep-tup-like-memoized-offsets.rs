@@ -0,0 +1,26 @@
+// compute_off, inside GEP_tup_like, now memoizes size_of/align_of per
+// fn_ctxt along the dynamically-sized path instead of re-emitting the
+// tydesc-driven IR at every field access. Whether the redundant IR
+// actually went away is an instruction-count question, out of reach for
+// a run-pass test. A memoization cache breaks a different way, though:
+// by serving a stale answer, an offset computed for the wrong field or
+// the wrong monomorphization of quad. So this walks every field of a
+// mixed-type tuple across a hundred iterations, which would turn a
+// stale cache hit into a wrong field value instead of a crash.
+type quad<A, B> = {a: A, b: A, c: B, d: A};
+
+fn sum<A: copy, B: copy>(q: quad<A, B>, to_int: fn(A) -> int,
+                         b_to_int: fn(B) -> int) -> int {
+    ret to_int(q.a) + to_int(q.b) + b_to_int(q.c) + to_int(q.d);
+}
+
+fn main() {
+    let q: quad<int, u8> = {a: 1, b: 2, c: 3u8, d: 4};
+    let total = 0;
+    let i = 0;
+    while i < 100 {
+        total += sum(q, {|x| x}, {|x| x as int});
+        i += 1;
+    }
+    assert total == 1000;
+}
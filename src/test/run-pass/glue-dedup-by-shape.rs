@@ -0,0 +1,18 @@
+// Drop glue is now emitted per-shape instead of per-type: two structurally
+// identical records (here, both a pair of boxes) share one drop glue
+// function rather than each getting their own. There's no way from
+// within Rust to confirm pair_a and pair_b actually resolved to the same
+// LLVM symbol -- that's a fact about the compiled output, not the
+// program's behavior. So the assertions below target the failure mode
+// sharing introduces instead: a shape collision papering over which
+// record's fields are which, dropping the wrong pair of boxes when
+// *a.a + *a.b and *b.x + *b.y come back.
+type pair_a = {a: @int, b: @int};
+type pair_b = {x: @int, y: @int};
+
+fn main() {
+    let a: pair_a = {a: @1, b: @2};
+    let b: pair_b = {x: @3, y: @4};
+    assert *a.a + *a.b == 3;
+    assert *b.x + *b.y == 7;
+}
@@ -0,0 +1,11 @@
+fn main() {
+    let x = if true { 1 } else { 2 };
+    assert x == 1;
+    let y = if false { 1 } else { 2 };
+    assert y == 2;
+
+    let a = 10;
+    let b = 20;
+    let z = if true { a } else { b };
+    assert z == 10;
+}
@@ -0,0 +1,7 @@
+use std;
+import str;
+
+fn main() {
+    assert str::eq(#include_str["include-str.txt"],
+                   "hello from a fixture file\n");
+}
@@ -0,0 +1,28 @@
+// register_fn_fuller reads #[inline]/#[inline(always)]/#[inline(never)]
+// off a fn's attrs and applies the matching LLVM function attribute --
+// inlinehint, alwaysinline, or noinline -- and, new here, gives a bare
+// #[inline] or #[inline(always)] fn linkonce_odr linkage instead of its
+// default external linkage, so a downstream crate that inlines it
+// doesn't collide with another crate's copy of the same body. That
+// linkage change is a cross-crate concern by definition, and exercising
+// it for real would need an aux-build-style two-crate test this tree
+// doesn't have. Short of that, calling each of the three attributed
+// functions from more than one call site in a single crate at least
+// confirms the attribute logic left their results alone -- it says
+// nothing about whether linkonce_odr actually landed on add/mul's
+// symbol.
+#[inline]
+fn add(a: int, b: int) -> int { ret a + b; }
+
+#[inline(always)]
+fn mul(a: int, b: int) -> int { ret a * b; }
+
+#[inline(never)]
+fn sub(a: int, b: int) -> int { ret a - b; }
+
+fn main() {
+    assert add(2, 3) == 5;
+    assert add(add(2, 3), 1) == 6;
+    assert mul(2, 3) == 6;
+    assert sub(5, 3) == 2;
+}
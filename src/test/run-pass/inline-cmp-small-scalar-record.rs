@@ -0,0 +1,29 @@
+// trans_compare now special-cases records and tuples whose fields are all
+// scalar and few enough (see fields_for_inline_cmp's inline_cmp_max_fields)
+// by GEP'ing each field and chaining compare_scalar_types directly, instead
+// of always going through the call_cmp_glue upcall. Exercise every
+// comparison operator across enough field positions to catch an off-by-one
+// in the lexicographic fold (first field decisive, first field tied and
+// second decisive, and fully equal).
+fn main() {
+    assert {x: 1, y: 2} == {x: 1, y: 2};
+    assert {x: 1, y: 2} != {x: 1, y: 3};
+    assert {x: 1, y: 2} != {x: 2, y: 2};
+
+    assert {x: 1, y: 2} < {x: 1, y: 3};
+    assert {x: 1, y: 2} < {x: 2, y: 0};
+    assert {x: 1, y: 2} <= {x: 1, y: 2};
+    assert {x: 1, y: 3} > {x: 1, y: 2};
+    assert {x: 2, y: 0} > {x: 1, y: 2};
+    assert {x: 1, y: 2} >= {x: 1, y: 2};
+
+    assert (1, 2, 3) == (1, 2, 3);
+    assert (1, 2, 3) < (1, 2, 4);
+    assert (1, 2, 4) > (1, 2, 3);
+    assert (1, 3, 0) > (1, 2, 9);
+
+    // A record whose only field is a nested record isn't all-scalar, so it
+    // still has to fall back to cmp glue; make sure that path still works.
+    assert {inner: {x: 1, y: 2}} == {inner: {x: 1, y: 2}};
+    assert {inner: {x: 1, y: 2}} != {inner: {x: 1, y: 3}};
+}
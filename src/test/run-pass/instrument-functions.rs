@@ -0,0 +1,36 @@
+// compile-flags:--instrument-functions
+
+// Under --instrument-functions, trans_closure/finish_fn (trans::base)
+// wrap every function body with calls to the gcc-compatible
+// __cyg_profile_func_enter/__cyg_profile_func_exit hooks now, declared
+// with a plain two-pointer/void C signature via get_extern_fn the same
+// way __cxa_begin_catch/__cxa_end_catch already are. A `crust fn` is
+// this dialect's way to define a symbol with that C calling convention,
+// so this crate supplies its own #[no_mangle] crust fn definitions of
+// the two hooks -- enough to drive the feature end to end. Those hooks
+// are no-ops below, so nothing here can tell "the calls were emitted
+// and did nothing" apart from "the calls were never emitted"; that
+// distinction needs either the IR or a hook able to reach back into
+// caller state, and crust fn's fixed two-pointer signature leaves no
+// room for the latter. What this does catch is a sharper failure mode:
+// instrumenting the hooks' own bodies would recurse forever, hanging or
+// stack-overflowing instead of returning, so reaching main's assert at
+// all is a real, if coarse, signal that self-instrumentation is being
+// skipped correctly.
+//
+// trans_instrument_call skips instrumenting the hooks' own bodies
+// (instrument_functions_should_skip): without that, a crate implementing
+// these two symbols and compiling them with this same flag would recurse
+// into itself forever, the same hazard gcc's -finstrument-functions has.
+
+#[no_mangle]
+crust fn __cyg_profile_func_enter(_this_fn: *u8, _call_site: *u8) { }
+
+#[no_mangle]
+crust fn __cyg_profile_func_exit(_this_fn: *u8, _call_site: *u8) { }
+
+fn add(a: int, b: int) -> int { ret a + b; }
+
+fn main() {
+    assert add(1, 2) == 3;
+}
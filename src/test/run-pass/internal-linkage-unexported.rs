@@ -0,0 +1,25 @@
+// register_fn_fuller, in trans/base.rs, gives an ordinary fn internal
+// linkage now whenever it's unreachable from the crate root through a
+// chain of `export`s -- compute_reachable_items decides that -- and
+// carries no #[linkage]/#[no_mangle] of its own, so an unused one can
+// genuinely be dropped by a --gc-sections-style link. That new default
+// only changes how the symbol looks to a linker resolving translation
+// units against each other, which is invisible to a single-crate
+// run-pass test by construction. The risk actually worth guarding here
+// is different: compute_reachable_items misjudging reachability and
+// marking something internal that the crate still calls from within
+// itself. Internal linkage alone wouldn't stop that intra-crate call,
+// but the same reachability computation feeds other passes (dead code
+// elimination among them) where a bug like that would bite harder. So
+// `secret_helper` is only ever called indirectly here, through the
+// exported `public` -- the shape a real unexported-but-still-used
+// helper would take.
+export public;
+
+fn secret_helper(x: int) -> int { ret x * 2; }
+
+fn public(x: int) -> int { ret secret_helper(x) + 1; }
+
+fn main() {
+    assert public(20) == 41;
+}
@@ -0,0 +1,26 @@
+// alloc_local brackets each static-size local's alloca with
+// llvm.lifetime.start/end now, letting two large locals in disjoint
+// sibling blocks share a stack slot rather than each getting its own.
+// Measuring whether the frame actually shrank isn't something a running
+// program can do. A misplaced marker is, though: if lifetime.end fired
+// on `x` before `total` finished reading it, `x`'s slot could already be
+// handed to `y` and the sum below would come out wrong -- that's the
+// failure this is pointed at.
+type big = {a: int, b: int, c: int, d: int, e: int, f: int, g: int, h: int};
+
+fn sum(x: big) -> int {
+    ret x.a + x.b + x.c + x.d + x.e + x.f + x.g + x.h;
+}
+
+fn main() {
+    let mutable total: int = 0;
+    {
+        let x: big = {a: 1, b: 2, c: 3, d: 4, e: 5, f: 6, g: 7, h: 8};
+        total += sum(x);
+    }
+    {
+        let y: big = {a: 8, b: 7, c: 6, d: 5, e: 4, f: 3, g: 2, h: 1};
+        total += sum(y);
+    }
+    assert total == 72;
+}
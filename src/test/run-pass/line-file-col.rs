@@ -0,0 +1,10 @@
+use std;
+import str;
+
+fn main() {
+    let the_line = #line; // <-- this is line 5
+    assert the_line == 5;
+    assert str::ends_with(#file, "line-file-col.rs");
+    let the_col = #col;
+    assert the_col > 0;
+}
@@ -0,0 +1,18 @@
+// #[linkage = "..."] on a fn or static drives an explicit SetLinkage call
+// in register_fn_fuller/collect_item now, in place of the implicit
+// internal/external choice those functions used to make unassisted.
+// Whether the linker actually treats weak_fn/weak_static as weak symbols
+// only matters once a second translation unit defines the same names,
+// which a single-crate test has no way to set up. What a single-crate
+// test can still catch is a regression in the surrounding logic, so a
+// weak fn and a weak static are both read back here to confirm the new
+// SetLinkage call didn't disturb anything else on that path.
+#[linkage = "weak"]
+fn weak_fn(x: int) -> int { ret x + 1; }
+
+#[linkage = "weak"]
+const weak_static: int = 41;
+
+fn main() {
+    assert weak_fn(weak_static) == 42;
+}
@@ -0,0 +1,7 @@
+// #log_syntax(e) now returns e unchanged (after printing it), instead of a
+// throwaway nil expr, so it's transparent enough to wrap any subexpression.
+fn double(x: int) -> int { ret x * 2; }
+
+fn main() {
+    assert #log_syntax(double(21)) == 42;
+}
@@ -0,0 +1,10 @@
+// memmove_ty now passes the struct's real alignment to llvm.memmove
+// instead of hard-coding 1; this just exercises a struct copy that goes
+// through that path.
+type point = {x: int, y: int};
+
+fn main() {
+    let a: point = {x: 1, y: 2};
+    let b = a;
+    assert b.x == 1 && b.y == 2;
+}
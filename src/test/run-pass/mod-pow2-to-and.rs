@@ -0,0 +1,7 @@
+fn main() {
+    let h = 37u;
+    assert h % 16u == h & 15u;
+    assert h / 16u == h >> 4u;
+    assert 0u % 16u == 0u;
+    assert 15u % 16u == 15u;
+}
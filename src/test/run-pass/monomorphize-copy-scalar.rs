@@ -0,0 +1,20 @@
+// compile-flags:--monomorphize
+
+// Under --monomorphize, monomorphic_fn compiles a separate copy of a
+// generic function's body per concrete type argument now, substituting
+// the type parameter throughout via node_id_type/expr_ty (monomorphic_fn's
+// own doc comment in trans/base.rs has the details). Once T becomes a
+// scalar like int, copy_val_no_check/take_ty/drop_ty see a plain scalar
+// ty::t and take their existing glue-free fast paths, so dup<T: copy>
+// monomorphized at int should emit no tydesc glue calls and no self-copy
+// check for `x` -- confirming that means reading the generated IR,
+// which isn't available here. Absent that, this checks the thing that
+// would actually surface as wrong output: whether substituting T with
+// int all the way through monomorphic_fn's copy left a stale reference
+// to the original generic ty::t somewhere, which would make dup(42)
+// return something other than 42 rather than fail to compile.
+fn dup<T: copy>(x: T) -> T { let y = x; ret y; }
+
+fn main() {
+    assert dup(42) == 42;
+}
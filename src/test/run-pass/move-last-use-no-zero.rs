@@ -0,0 +1,23 @@
+// move_val consults last_uses before zero_alloca/memset runs, and skips
+// zeroing -- revoking drop-glue instead -- once it's sure the moved-from
+// slot won't be read again. A single move takes the same visible path
+// whether the revocation logic is right or wrong; the bug only shows up
+// statistically, when the wrong slot's cleanup gets revoked and its
+// stale drop-glue flag fires a second time down the line. So the loop
+// below runs a resource through a hundred move-heavy iterations and
+// checks its counter lands on exactly 100: overshooting means a
+// double-drop slipped through, undershooting means one got skipped.
+resource counter(n: @mutable int) { *n += 1; }
+
+fn main() {
+    let drops = @mutable 0;
+    let i = 0;
+    while i < 100 {
+        let c <- counter(drops);
+        // `c` is never read again after this move, so its slot's
+        // cleanup should be revoked rather than zeroed.
+        let moved <- c;
+        i += 1;
+    }
+    assert *drops == 100;
+}
@@ -0,0 +1,17 @@
+// An extern "C" function is now emitted `nounwind` by default (see
+// set_nounwind/build_shim_fn in trans/native.rs), since a stray unwind
+// crossing back into Rust from one is UB. #[unwind] opts a specific
+// function back into being callable as unwindable. Neither attribute is
+// observable without inspecting the emitted IR, so this only confirms
+// both compile and can be called without upsetting codegen.
+#[abi = "cdecl"]
+native mod libc {
+    fn abs(n: int) -> int;
+    #[unwind]
+    fn qsort(base: *u8, nmemb: uint, width: uint, cmp: *u8);
+}
+
+fn main() {
+    assert abs(-5) == 5;
+    let _f = qsort;
+}
@@ -0,0 +1,15 @@
+// A setjmp-style function returns more than once, breaking the usual
+// single-return assumption trans_call_inner relies on. #[returns_twice]
+// tells LLVM not to make those assumptions across the call. The effect
+// (the ReturnsTwice function attribute) isn't observable without
+// inspecting the emitted IR, so this only confirms such a declaration
+// compiles and can be taken as a value without upsetting codegen.
+#[abi = "cdecl"]
+native mod libc {
+    #[returns_twice]
+    fn setjmp(env: *u8) -> int;
+}
+
+fn main() {
+    let _f = libc::setjmp;
+}
@@ -0,0 +1,16 @@
+// #[no_mangle] makes register_fn_fuller use an item's identifier
+// verbatim as its symbol now, skipping mangle_exported_name, and gives
+// it external linkage so C code or the runtime can find it by that
+// fixed name. The symbol table isn't something a running Rust program
+// can inspect, so the exact name it lands under goes unverified here;
+// what's left within reach is confirming the function still compiles
+// and behaves the same as a mangled one would. Two #[no_mangle] items
+// colliding on a name is a link error, and no single well-formed
+// program can trigger that from the front end alone, so that case
+// stays out of scope too.
+#[no_mangle]
+fn rust_add_one(x: int) -> int { ret x + 1; }
+
+fn main() {
+    assert rust_add_one(41) == 42;
+}
@@ -0,0 +1,22 @@
+// trans_closure now takes a maybe_emit_prologue: fn(fn_ctxt) callback,
+// run in fcx.llstaticallocas ahead of copy_args_to_allocas, so future
+// runtime-support code can splice in a few custom setup instructions at
+// function entry without the full restrictions #[naked] would impose.
+// This extension point isn't dead: all three call sites now pass
+// maybe_trans_instrument_enter (see --instrument-functions,
+// instrument-functions.rs), which does emit real prologue instructions
+// when that flag is on. This test compiles without the flag, so
+// maybe_trans_instrument_enter's own internal check keeps it a no-op
+// here -- what's being checked is that threading the parameter through
+// trans_closure's default (flag-off) path didn't perturb two of the three
+// call sites: a plain fn and a block closure. The third, for a bare fn
+// expression (closure.rs's proto_bare arm), is not exercised here.
+// See instrument-functions.rs for the case where the hook actually
+// fires.
+fn add(a: int, b: int) -> int { ret a + b; }
+
+fn main() {
+    assert add(2, 3) == 5;
+    let f = {|x: int| x + 1};
+    assert f(41) == 42;
+}
@@ -0,0 +1,34 @@
+// compile-flags:--relative-vtables
+
+// Under --relative-vtables, trans_vtable (trans/impl.rs) emits impl
+// vtables as arrays of 32-bit offsets from the vtable to each method now,
+// rather than absolute function pointers, and trans_vtable_entry adds
+// that offset back to the vtable's own address at each dispatch site
+// (trans_vtable_callee, trans_iface_wrapper) to recover something
+// callable. Reading the emitted global's initializer would be needed to
+// confirm the slots actually hold offsets and not raw pointers, and
+// that's not available here. The offset/recovery split fails loudly
+// rather than quietly when it's wrong, though: a trans_vtable_entry that
+// forgets to add the vtable's base address back in ends up calling
+// through a small bogus integer instead of a function pointer. So
+// dispatching successfully through a generic bound (`indirect`, which
+// reaches trans_vtable_callee through its dict) is a meaningful check
+// that the two halves of that pair still agree. `1.to_str()` resolves to
+// the impl statically and reads no vtable; no boxed iface is dispatched
+// here.
+iface to_str {
+    fn to_str() -> str;
+}
+
+impl of to_str for int {
+    fn to_str() -> str { int::str(self) }
+}
+
+fn indirect<T: to_str>(x: T) -> str {
+    x.to_str() + "!"
+}
+
+fn main() {
+    assert 1.to_str() == "1";
+    assert indirect(2) == "2!";
+}
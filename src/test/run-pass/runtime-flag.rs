@@ -0,0 +1,22 @@
+// #[runtime_flag = "..."] generalizes the check_claims mechanism (see
+// trans::base::get_runtime_flag_global): the const's global is declared
+// but never given an initializer, so the runtime is free to flip it, and
+// ordinary references to the const Load from that same global rather than
+// getting folded to a compile-time value.
+//
+// There's no RTS-side test infra in this tree to actually flip the flag
+// from outside the process (no auxiliary C stubs for run-pass tests, and
+// this dialect has no way to take the address of an extern const from
+// Rust source), so this can't exercise "toggling the flag changes which
+// branch executes" end to end. It exercises the const-reference codegen
+// path instead: the flag starts false (its LLVM global is zero-initialized
+// like any other uninitialized global), and reading it selects the
+// expected branch.
+#[runtime_flag = "test_debug_flag"]
+const test_debug_flag: bool = false;
+
+fn main() {
+    if test_debug_flag {
+        fail "runtime flag should default to false";
+    }
+}
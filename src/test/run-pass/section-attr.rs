@@ -0,0 +1,19 @@
+// #[section = "..."] on a const drives an explicit LLVMSetSection call
+// in collect_item's item_const arm now, the same way write_metadata
+// already sets a section on the metadata global. Which section the
+// linked binary actually places the global in is strictly an
+// objdump/readelf question. What's worth checking from a running
+// program is narrower: whether stacking a second SetSection call onto
+// the code path #[linkage] already touches reorders or drops either
+// attribute's effect -- so `sectioned_weak` below carries both
+// #[section] and #[linkage] at once.
+#[section = ".mytext"]
+const sectioned: int = 41;
+
+#[section = ".mytext"]
+#[linkage = "weak"]
+const sectioned_weak: int = 1;
+
+fn main() {
+    assert sectioned + sectioned_weak == 42;
+}
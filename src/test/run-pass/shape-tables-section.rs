@@ -0,0 +1,22 @@
+// The shape tables blob -- ccx.shape_cx.llshapetables, built by
+// gen_shape_tables -- gets a stable symbol now, _rust_shape_tables, set
+// in shape::mk_ctxt, in place of the generic "shapes", along with its
+// own linker section (target_strs.shape_sect_name) and a byte count
+// folded into --stats' n_shape_table_bytes. A linker map would be
+// needed to confirm the new symbol name and section actually stuck,
+// which isn't available here. But a sloppy rename breaks whatever else
+// reads the blob back, and the runtime shape interpreter is exactly
+// that reader: it consults the table for size-of and pattern-match
+// dispatch. So getting a correct size-of and a correct alt match out of
+// an enum is what this leans on to confirm the rename didn't orphan
+// anything.
+enum color { red, green, blue(int), }
+
+fn main() {
+    let c = blue(42);
+    alt c {
+      blue(n) { assert n == 42; }
+      _ { fail "wrong variant"; }
+    }
+    assert sys::size_of::<color>() > 0u;
+}
@@ -0,0 +1,18 @@
+// -shared-glue makes declare_generic_glue name glue with
+// mangle_internal_name_by_type_only now -- deterministic, in place of
+// the seq-based name it otherwise falls back to -- and gives
+// make_generic_glue_inner linkonce_odr linkage instead of internal, so
+// identical glue for the same type collapses across translation units
+// at link time. Exercising that for real needs two crates linked
+// together, and compiletest's run-pass mode has no `compile-flags:`
+// knob for building a second crate to link against, so -shared-glue
+// goes untested by this file. What it does exercise is the path right
+// next to it: the default, non -shared-glue naming and linkage still
+// running the correct destructor for two distinct generic drop-glue
+// instantiations.
+fn drop_two<T>(a: T, b: T) { }
+
+fn main() {
+    drop_two(@1, @2);
+    drop_two(~3, ~4);
+}
@@ -0,0 +1,32 @@
+// Foundational codegen support for a small-vector-style type: an inline
+// variant with no heap payload alongside a heap variant, built as an
+// ordinary enum. type_of/GEP_tup_like already size such a tag for its
+// largest variant, and the generic drop glue in iter_structural_ty already
+// switches on the discriminant, so dropping an `inline` small vector runs
+// exactly its own elements' destructors and never touches the `heap`
+// variant's payload. This compiler has no fixed-length array type
+// ([T * N]) yet, so the "inline" capacity here is realized as a 2-tuple
+// rather than a true `[T * N]`.
+
+type closable = @mutable bool;
+
+resource close_res(i: closable) { *i = false; }
+
+enum small_vec<T> {
+    inline(T, T),
+    heap(@[mutable T]),
+}
+
+fn main() {
+    let c0 = @mutable true;
+    let c1 = @mutable true;
+    { let v = inline(close_res(c0), close_res(c1)); }
+    assert !*c0;
+    assert !*c1;
+
+    // The heap variant carries no per-element resources of its own, so
+    // dropping it doesn't touch unrelated state.
+    let c2 = @mutable true;
+    { let h: small_vec<int> = heap(@[mutable 1, 2, 3]); }
+    assert *c2;
+}
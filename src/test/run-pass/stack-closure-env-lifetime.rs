@@ -0,0 +1,32 @@
+// A `{|...| ...}` block-proto closure's environment -- ty::ck_block, in
+// closure::allocate_cbox -- is an ordinary stack alloca rather than a
+// heap box, so its lifetime is the enclosing scope, not "until the
+// closure is dropped". allocate_cbox now brackets it with the same
+// llvm.lifetime.start/end markers base::alloc_local already gives a
+// plain local, which lets LLVM reuse the slot once the scope exits.
+// Whether LLVM actually reused it is an optimization outcome this test
+// has no way to see. What it's built to catch is the correctness risk on
+// the other side: a lifetime.end landing before the closure is done
+// reading its own environment, which would show up as the captured
+// values (a, b, c, d) going stale mid-call. So the loop below pushes a
+// hundred distinct environments through the same call site, each one
+// depending on all four captures to produce a distinct sum -- a slot
+// reused one iteration too early would throw the total off.
+fn apply(f: fn(int) -> int, x: int) -> int { f(x) }
+
+fn main() {
+    let mutable total = 0;
+    let mutable i = 0;
+    while i < 100 {
+        let a = i, b = i * 2, c = i * 3, d = i * 4;
+        total += apply({|x| x + a + b + c + d }, i);
+        i += 1;
+    }
+    let mutable expect = 0;
+    i = 0;
+    while i < 100 {
+        expect += i + i + i * 2 + i * 3 + i * 4;
+        i += 1;
+    }
+    assert total == expect;
+}
@@ -0,0 +1,7 @@
+use std;
+import str;
+
+fn main() {
+    let a = 1, b = 2;
+    assert str::eq(#stringify(a + b), "a + b");
+}
@@ -0,0 +1,20 @@
+// compile-flags:--data-layout=e-p:64:64:64-i1:8:8-i8:8:8-i16:16:16-i32:32:32-i64:64:64-f32:32:32-f64:64:64-v64:64:64-v128:128:128-a0:0:64-s0:64:64-f80:128:128-n8:16:32:64-S128
+
+// session::options::target_data_layout, in trans::base::trans_crate, now
+// lets a target sub-variant supply its own data layout string in place
+// of the fixed one in target_strs::t -- threaded into both
+// LLVMSetDataLayout and mk_target_data so the two can't disagree with
+// each other. The module's IR header isn't visible from a running
+// program, so whether it actually carries the overridden string goes
+// unchecked directly. Its two consumers are reachable indirectly,
+// though: LLVMSetDataLayout governs how LLVM lays out and aligns struct
+// fields, and mk_target_data feeds shape::size_of's runtime size/offset
+// math. The layout string passed below is deliberately x86_64's own
+// default, chosen so the values here stay meaningful, and a multi-field
+// tuple's fields landing where size_of-driven codegen expects them is
+// what that buys.
+fn main() {
+    let tup = (1u8, 2u16, 3u32, 4u64);
+    assert tup.0 == 1u8;
+    assert tup.3 == 4u64;
+}
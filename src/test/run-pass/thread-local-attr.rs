@@ -0,0 +1,18 @@
+// #[thread_local] on a const calls LLVMSetThreadLocal on its global from
+// collect_item now, instead of leaving it an ordinary process-wide
+// global -- and needed no further change in trans_var's def_const case
+// or load_if_immediate, since both already route every const's global
+// through a plain Load/Store rather than folding it to a constant.
+// Confirming the thread-local model bit landed on the global means
+// inspecting the emitted IR, which is out of reach here, and even with
+// that access a single-threaded test wouldn't be able to tell a
+// thread-local global apart from an ordinary one -- both read back the
+// same value below. What's actually being confirmed: routing the read
+// through LLVM's thread-local addressing instead of a plain address
+// didn't itself break the load.
+#[thread_local]
+const counter: int = 41;
+
+fn main() {
+    assert counter == 41;
+}
@@ -0,0 +1,19 @@
+// emit_tydescs folds tydescs that are structurally identical -- same
+// size, align, glue functions, and shape -- onto one global now, since
+// monomorphization would otherwise stamp out many copies that differ
+// only in which ty::t produced them. Counting the resulting distinct
+// tydesc globals needs a linker map or an IR dump, neither available
+// here. Folding two tydescs together is only sound if their glue is
+// genuinely interchangeable, though, so `id` gets instantiated at two
+// structurally identical but semantically distinct pointer types (@int
+// and @str), and each value has to survive the round trip -- a shared
+// tydesc that mixed the two glues up would show here as one of them
+// coming back wrong.
+fn id<T: copy>(x: T) -> T { ret x; }
+
+fn main() {
+    let a: @int = id(@1);
+    let b: @str = id(@"hi");
+    assert *a == 1;
+    assert *b == "hi";
+}
@@ -0,0 +1,21 @@
+// declare_tydesc now records whether a type statically needs no take/drop/
+// free glue (ty::type_needs_drop == false, e.g. int or a record of ints),
+// and emit_tydescs fills those tydesc slots with null instead of ever
+// declaring a trivial glue function for them. A generic function's body is
+// compiled once and drops its by-tydesc-parameter values through a runtime
+// (not statically known) tydesc, so call_tydesc_glue_full has to tolerate
+// a null glue pointer there -- this exercises exactly that path for a
+// scalar, a scalar record, and (for contrast) a real boxed value whose
+// glue must still run.
+fn drop_generic<T: copy>(t: T) { let _t1: T = t; }
+
+fn main() {
+    drop_generic(1);
+    drop_generic({x: 1, y: 2});
+    drop_generic(@10);
+    drop_generic(~20);
+
+    let b = @30;
+    drop_generic(b);
+    assert *b == 30;
+}
@@ -0,0 +1,14 @@
+// Exercises the `unpredictable` rust-intrinsic used by trans to tag the
+// branch on this condition with `!unpredictable` metadata (there's no IR
+// inspection in this test harness, so this just checks the value still
+// flows through correctly).
+use std;
+
+fn main() {
+    let x = 4;
+    if sys::unpredictable(x % 2 == 0) {
+        assert x % 2 == 0;
+    } else {
+        fail;
+    }
+}
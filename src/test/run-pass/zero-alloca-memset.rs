@@ -0,0 +1,18 @@
+// zero_alloca now memsets a statically-sized local bigger than a
+// handful of words -- using the type's real alignment -- rather than
+// storing a giant C_null literal; small locals still go through a plain
+// Store. Which path fired for `x` is an IR-level detail this test can't
+// see. What it can catch is a wrong alignment reaching that memset,
+// which is the specific way this change could misbehave without ever
+// crashing. So `x` is left uninitialized here, forcing the memset path,
+// then fully assigned and read back field by field -- a misaligned
+// memset would turn up as corrupted values rather than a fault.
+type big = {a: int, b: int, c: int, d: int, e: int, f: int,
+            g: int, h: int, i: int, j: int, k: int, l: int};
+
+fn main() {
+    let x: big;
+    x = {a: 1, b: 2, c: 3, d: 4, e: 5, f: 6,
+         g: 7, h: 8, i: 9, j: 10, k: 11, l: 12};
+    assert x.a == 1 && x.f == 6 && x.l == 12;
+}